@@ -0,0 +1,91 @@
+//! Allocations and time of the named key lookup in `Key::new`.
+//!
+//! ```sh
+//! cargo bench --bench named_keys
+//! ```
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::hint::black_box;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Instant;
+
+use viks::Key;
+
+struct CountingAlloc;
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for CountingAlloc {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        unsafe { System.alloc(layout) }
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        unsafe { System.dealloc(ptr, layout) }
+    }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAlloc = CountingAlloc;
+
+const ITERATIONS: usize = 100_000;
+
+/// Run `f` for each tag, and get the allocations per call and the time per call.
+fn measure(tags: &[&str], f: impl Fn(&str)) -> (f64, f64) {
+    let calls = (ITERATIONS * tags.len()) as f64;
+    let before = ALLOCATIONS.load(Ordering::Relaxed);
+    let start = Instant::now();
+
+    for _ in 0..ITERATIONS {
+        for tag in tags {
+            f(black_box(tag));
+        }
+    }
+
+    let elapsed = start.elapsed().as_nanos() as f64;
+    let allocations = (ALLOCATIONS.load(Ordering::Relaxed) - before) as f64;
+
+    (allocations / calls, elapsed / calls)
+}
+
+fn report(name: &str, tags: &[&str], f: impl Fn(&str)) -> f64 {
+    let (allocations, nanos) = measure(tags, f);
+
+    println!("{name:<36} {allocations:>6.2} allocs/call {nanos:>8.1} ns/call");
+
+    allocations
+}
+
+fn main() {
+    let named = [
+        "<CR>",
+        "<Enter>",
+        "<Esc>",
+        "<leader>",
+        "<BS>",
+        "<c-Space>",
+        "<F12>",
+        "<ScrollWheelDown>",
+        "<kMultiply>",
+        "<s-Tab>",
+    ];
+    let too_long = ["<ThisIsNotAKeyNameAtAll>", "<c-AnotherVeryLongName>"];
+
+    let lookup = report("named keys", &named, |tag| {
+        black_box(Key::new(tag).unwrap());
+    });
+
+    // the lowercased copy of the name was allocated before the lookup
+    report("named keys, lowercase copy", &named, |tag| {
+        black_box(tag.to_lowercase());
+        black_box(Key::new(tag).unwrap());
+    });
+
+    // the error itself allocates, the rejection before the lookup doesn't
+    report("too long names, rejected", &too_long, |tag| {
+        black_box(Key::new(tag).unwrap_err());
+    });
+
+    assert_eq!(lookup, 0.0, "the named key lookup allocates");
+}
@@ -1,3 +1,5 @@
+use alloc::vec::Vec;
+
 #[repr(u8)]
 #[derive(Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
 pub(crate) enum KeyCode {
@@ -75,18 +77,509 @@ pub(crate) enum KeyCode {
     RightCurlyBracket = 125,
     Tilde = 126,
     Delete = 127,
+    LeftMouse = 128,
+    MiddleMouse = 129,
+    RightMouse = 130,
+    ScrollWheelUp = 131,
+    ScrollWheelDown = 132,
+    F1 = 133,
+    F2 = 134,
+    F3 = 135,
+    F4 = 136,
+    F5 = 137,
+    F6 = 138,
+    F7 = 139,
+    F8 = 140,
+    F9 = 141,
+    F10 = 142,
+    F11 = 143,
+    F12 = 144,
+    F13 = 145,
+    F14 = 146,
+    F15 = 147,
+    F16 = 148,
+    F17 = 149,
+    F18 = 150,
+    F19 = 151,
+    F20 = 152,
+    F21 = 153,
+    F22 = 154,
+    F23 = 155,
+    F24 = 156,
+    Up = 157,
+    Down = 158,
+    Left = 159,
+    Right = 160,
+    Home = 161,
+    End = 162,
+    PageUp = 163,
+    PageDown = 164,
+    Insert = 165,
+    Nop = 166,
+    Plug = 167,
+    K0 = 168,
+    K1 = 169,
+    K2 = 170,
+    K3 = 171,
+    K4 = 172,
+    K5 = 173,
+    K6 = 174,
+    K7 = 175,
+    K8 = 176,
+    K9 = 177,
+    KeypadPlus = 178,
+    KeypadMinus = 179,
+    KeypadMultiply = 180,
+    KeypadDivide = 181,
+    KeypadEnter = 182,
+    Unicode = 183,
+    BackTab = 184,
+    LineFeed = 185,
+    Unknown = 186,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+#[non_exhaustive]
+/// Public key code of [Key](crate::Key).
+///
+/// Printable keys are `Char`, alphabetic is always lowercase and
+/// the shift is held in [KeyModifiers](crate::KeyModifiers).
+///
+/// # Example
+///
+/// ```
+/// use viks::{Code, Key};
+///
+/// # fn main() {
+/// assert_eq!(Key::new("<CR>").unwrap().code(), Code::Enter);
+/// assert_eq!(Key::new("a").unwrap().code(), Code::Char('a'));
+/// assert_eq!(Key::new("A").unwrap().code(), Code::Char('a'));
+/// assert_eq!(Key::new("<leader>").unwrap().code(), Code::Char(' '));
+/// # }
+/// ```
+pub enum Code {
+    /// Printable char, includes space and `<`.
+    Char(char),
+    /// Enter key, `<CR>`.
+    Enter,
+    /// Tab key.
+    Tab,
+    /// Escape key.
+    Esc,
+    /// Backspace key, `<BS>`.
+    Backspace,
+    /// Delete key, `<DEL>`.
+    Delete,
+    /// Left mouse button.
+    LeftMouse,
+    /// Middle mouse button.
+    MiddleMouse,
+    /// Right mouse button.
+    RightMouse,
+    /// Mouse wheel up.
+    ScrollWheelUp,
+    /// Mouse wheel down.
+    ScrollWheelDown,
+    /// Up arrow key.
+    Up,
+    /// Down arrow key.
+    Down,
+    /// Left arrow key.
+    Left,
+    /// Right arrow key.
+    Right,
+    /// Home key.
+    Home,
+    /// End key.
+    End,
+    /// Page up key.
+    PageUp,
+    /// Page down key.
+    PageDown,
+    /// Insert key.
+    Insert,
+    /// No-op pseudo key, `<Nop>`.
+    Nop,
+    /// Plugin mapping prefix pseudo key, `<Plug>`.
+    Plug,
+    /// Keypad plus key, `<kPlus>`.
+    KeypadPlus,
+    /// Keypad minus key, `<kMinus>`.
+    KeypadMinus,
+    /// Keypad multiply key, `<kMultiply>`.
+    KeypadMultiply,
+    /// Keypad divide key, `<kDivide>`.
+    KeypadDivide,
+    /// Keypad enter key, `<kEnter>`.
+    KeypadEnter,
+    /// Shift-Tab key, `<BackTab>` or `<s-tab>`.
+    BackTab,
+    /// Line feed key, `<NL>`, the byte `0x0a`.
+    LineFeed,
+    /// Function key, `F(1)` to `F(24)`.
+    F(u8),
+    /// Keypad digit key, `Keypad(0)` to `Keypad(9)`.
+    Keypad(u8),
+    /// Unrecognized tag kept by the lenient parse, see [ParseOptions::strict](crate::ParseOptions::strict).
+    ///
+    /// The tag name is kept verbatim without the modifiers, e.g. `BOO` of `<c-BOO>`.
+    Unknown(&'static str),
+}
+
+impl From<KeyCode> for Code {
+    fn from(value: KeyCode) -> Self {
+        match value {
+            KeyCode::Enter => Code::Enter,
+            KeyCode::Tab => Code::Tab,
+            KeyCode::Esc => Code::Esc,
+            KeyCode::Backspace => Code::Backspace,
+            KeyCode::Delete => Code::Delete,
+            KeyCode::LeftMouse => Code::LeftMouse,
+            KeyCode::MiddleMouse => Code::MiddleMouse,
+            KeyCode::RightMouse => Code::RightMouse,
+            KeyCode::ScrollWheelUp => Code::ScrollWheelUp,
+            KeyCode::ScrollWheelDown => Code::ScrollWheelDown,
+            KeyCode::Up => Code::Up,
+            KeyCode::Down => Code::Down,
+            KeyCode::Left => Code::Left,
+            KeyCode::Right => Code::Right,
+            KeyCode::Home => Code::Home,
+            KeyCode::End => Code::End,
+            KeyCode::PageUp => Code::PageUp,
+            KeyCode::PageDown => Code::PageDown,
+            KeyCode::Insert => Code::Insert,
+            KeyCode::Nop => Code::Nop,
+            KeyCode::Plug => Code::Plug,
+            KeyCode::KeypadPlus => Code::KeypadPlus,
+            KeyCode::KeypadMinus => Code::KeypadMinus,
+            KeyCode::KeypadMultiply => Code::KeypadMultiply,
+            KeyCode::KeypadDivide => Code::KeypadDivide,
+            KeyCode::KeypadEnter => Code::KeypadEnter,
+            KeyCode::BackTab => Code::BackTab,
+            KeyCode::LineFeed => Code::LineFeed,
+            keycode if keycode.is_function() => Code::F(keycode as u8 - KeyCode::F1 as u8 + 1),
+            keycode if keycode.is_keypad_digit() => Code::Keypad(keycode as u8 - KeyCode::K0 as u8),
+            keycode => Code::Char(keycode.as_ascii().to_ascii_lowercase()),
+        }
+    }
 }
 
+pub(crate) const NAMED_KEYS: &[(&str, KeyCode)] = &[
+    ("enter", KeyCode::Enter),
+    ("cr", KeyCode::Enter),
+    ("tab", KeyCode::Tab),
+    ("esc", KeyCode::Esc),
+    ("leader", KeyCode::Space),
+    ("space", KeyCode::Space),
+    ("bs", KeyCode::Backspace),
+    ("del", KeyCode::Delete),
+    ("lt", KeyCode::LessThanSign),
+    ("bar", KeyCode::VirticalLine),
+    ("bslash", KeyCode::ReverseSolidas),
+    ("leftmouse", KeyCode::LeftMouse),
+    ("middlemouse", KeyCode::MiddleMouse),
+    ("rightmouse", KeyCode::RightMouse),
+    ("scrollwheelup", KeyCode::ScrollWheelUp),
+    ("scrollwheeldown", KeyCode::ScrollWheelDown),
+    ("f1", KeyCode::F1),
+    ("f2", KeyCode::F2),
+    ("f3", KeyCode::F3),
+    ("f4", KeyCode::F4),
+    ("f5", KeyCode::F5),
+    ("f6", KeyCode::F6),
+    ("f7", KeyCode::F7),
+    ("f8", KeyCode::F8),
+    ("f9", KeyCode::F9),
+    ("f10", KeyCode::F10),
+    ("f11", KeyCode::F11),
+    ("f12", KeyCode::F12),
+    ("f13", KeyCode::F13),
+    ("f14", KeyCode::F14),
+    ("f15", KeyCode::F15),
+    ("f16", KeyCode::F16),
+    ("f17", KeyCode::F17),
+    ("f18", KeyCode::F18),
+    ("f19", KeyCode::F19),
+    ("f20", KeyCode::F20),
+    ("f21", KeyCode::F21),
+    ("f22", KeyCode::F22),
+    ("f23", KeyCode::F23),
+    ("f24", KeyCode::F24),
+    ("up", KeyCode::Up),
+    ("down", KeyCode::Down),
+    ("left", KeyCode::Left),
+    ("right", KeyCode::Right),
+    ("home", KeyCode::Home),
+    ("khome", KeyCode::Home),
+    ("end", KeyCode::End),
+    ("kend", KeyCode::End),
+    ("pageup", KeyCode::PageUp),
+    ("pagedown", KeyCode::PageDown),
+    ("insert", KeyCode::Insert),
+    ("nop", KeyCode::Nop),
+    ("plug", KeyCode::Plug),
+    ("k0", KeyCode::K0),
+    ("k1", KeyCode::K1),
+    ("k2", KeyCode::K2),
+    ("k3", KeyCode::K3),
+    ("k4", KeyCode::K4),
+    ("k5", KeyCode::K5),
+    ("k6", KeyCode::K6),
+    ("k7", KeyCode::K7),
+    ("k8", KeyCode::K8),
+    ("k9", KeyCode::K9),
+    ("kplus", KeyCode::KeypadPlus),
+    ("kminus", KeyCode::KeypadMinus),
+    ("kmultiply", KeyCode::KeypadMultiply),
+    ("kdivide", KeyCode::KeypadDivide),
+    ("kenter", KeyCode::KeypadEnter),
+    ("backtab", KeyCode::BackTab),
+    ("nl", KeyCode::LineFeed),
+    ("newline", KeyCode::LineFeed),
+    ("linefeed", KeyCode::LineFeed),
+    ("lf", KeyCode::LineFeed),
+];
+
+const MAX_NAME_LEN: usize = 15;
+
 impl KeyCode {
-    pub(crate) fn from_ascii(ascii: u8) -> KeyCode {
-        if !matches!(ascii, 0..128) {
-            panic!("not ascii");
+    /// Get the KeyCode of the discriminant, the inverse of `as u8`.
+    pub(crate) fn from_repr(repr: u8) -> Option<KeyCode> {
+        KeyCode::from_ascii(repr)
+            .into_iter()
+            .chain(NAMED_KEYS.iter().map(|(_, code)| *code))
+            .find(|code| *code as u8 == repr)
+    }
+
+    pub(crate) fn from_name(name: &str) -> Option<KeyCode> {
+        if name.len() > MAX_NAME_LEN || !name.starts_with(|c: char| c.is_ascii_alphabetic()) {
+            return None;
+        }
+
+        NAMED_KEYS
+            .iter()
+            .find(|(n, _)| name.eq_ignore_ascii_case(n))
+            .map(|(_, code)| *code)
+    }
+
+    pub(crate) fn from_ascii(ascii: u8) -> Option<KeyCode> {
+        let code = match ascii {
+            8 => KeyCode::Backspace,
+            9 => KeyCode::Tab,
+            13 => KeyCode::Enter,
+            27 => KeyCode::Esc,
+            32 => KeyCode::Space,
+            33 => KeyCode::ExclamationMark,
+            34 => KeyCode::QuotationMark,
+            35 => KeyCode::NumberSign,
+            36 => KeyCode::DollarSign,
+            37 => KeyCode::PercentSign,
+            38 => KeyCode::Ampersand,
+            39 => KeyCode::Apostrophe,
+            40 => KeyCode::LeftParenthesis,
+            41 => KeyCode::RightParenthesis,
+            42 => KeyCode::Asterisk,
+            43 => KeyCode::PlusSign,
+            44 => KeyCode::Comma,
+            45 => KeyCode::HyphenMinus,
+            46 => KeyCode::FullStop,
+            47 => KeyCode::Solidus,
+            48 => KeyCode::Digit0,
+            49 => KeyCode::Digit1,
+            50 => KeyCode::Digit2,
+            51 => KeyCode::Digit3,
+            52 => KeyCode::Digit4,
+            53 => KeyCode::Digit5,
+            54 => KeyCode::Digit6,
+            55 => KeyCode::Digit7,
+            56 => KeyCode::Digit8,
+            57 => KeyCode::Digit9,
+            58 => KeyCode::Colon,
+            59 => KeyCode::Semicolon,
+            60 => KeyCode::LessThanSign,
+            61 => KeyCode::EqualSign,
+            62 => KeyCode::GreaterThanSign,
+            63 => KeyCode::QuestionMark,
+            64 => KeyCode::CommercialAt,
+            65 => KeyCode::A,
+            66 => KeyCode::B,
+            67 => KeyCode::C,
+            68 => KeyCode::D,
+            69 => KeyCode::E,
+            70 => KeyCode::F,
+            71 => KeyCode::G,
+            72 => KeyCode::H,
+            73 => KeyCode::I,
+            74 => KeyCode::J,
+            75 => KeyCode::K,
+            76 => KeyCode::L,
+            77 => KeyCode::M,
+            78 => KeyCode::N,
+            79 => KeyCode::O,
+            80 => KeyCode::P,
+            81 => KeyCode::Q,
+            82 => KeyCode::R,
+            83 => KeyCode::S,
+            84 => KeyCode::T,
+            85 => KeyCode::U,
+            86 => KeyCode::V,
+            87 => KeyCode::W,
+            88 => KeyCode::X,
+            89 => KeyCode::Y,
+            90 => KeyCode::Z,
+            91 => KeyCode::LeftSquareBracket,
+            92 => KeyCode::ReverseSolidas,
+            93 => KeyCode::RightSquareBracket,
+            94 => KeyCode::CircumflexAccent,
+            95 => KeyCode::LowLine,
+            96 => KeyCode::GraveAccent,
+            123 => KeyCode::LeftCurlyBracket,
+            124 => KeyCode::VirticalLine,
+            125 => KeyCode::RightCurlyBracket,
+            126 => KeyCode::Tilde,
+            127 => KeyCode::Delete,
+            _ => return None,
+        };
+
+        Some(code)
+    }
+
+    pub(crate) fn suggest_name(name: &str) -> Option<&'static str> {
+        NAMED_KEYS
+            .iter()
+            // the distance is at least the difference of the lengths
+            .filter(|(n, _)| n.len().abs_diff(name.len()) <= 2)
+            .map(|(n, _)| (*n, edit_distance(name, n)))
+            .filter(|(n, dist)| *dist <= 2 && dist * 2 <= n.len())
+            .min_by_key(|(_, dist)| *dist)
+            .map(|(n, _)| n)
+    }
+
+    pub(crate) fn name(&self) -> Option<&'static str> {
+        let name = match self {
+            KeyCode::Enter => "CR",
+            KeyCode::Tab => "TAB",
+            KeyCode::Esc => "ESC",
+            KeyCode::Space => "SPACE",
+            KeyCode::Backspace => "BS",
+            KeyCode::Delete => "DEL",
+            KeyCode::LessThanSign => "LT",
+            KeyCode::LeftMouse => "LeftMouse",
+            KeyCode::MiddleMouse => "MiddleMouse",
+            KeyCode::RightMouse => "RightMouse",
+            KeyCode::ScrollWheelUp => "ScrollWheelUp",
+            KeyCode::ScrollWheelDown => "ScrollWheelDown",
+            KeyCode::F1 => "F1",
+            KeyCode::F2 => "F2",
+            KeyCode::F3 => "F3",
+            KeyCode::F4 => "F4",
+            KeyCode::F5 => "F5",
+            KeyCode::F6 => "F6",
+            KeyCode::F7 => "F7",
+            KeyCode::F8 => "F8",
+            KeyCode::F9 => "F9",
+            KeyCode::F10 => "F10",
+            KeyCode::F11 => "F11",
+            KeyCode::F12 => "F12",
+            KeyCode::F13 => "F13",
+            KeyCode::F14 => "F14",
+            KeyCode::F15 => "F15",
+            KeyCode::F16 => "F16",
+            KeyCode::F17 => "F17",
+            KeyCode::F18 => "F18",
+            KeyCode::F19 => "F19",
+            KeyCode::F20 => "F20",
+            KeyCode::F21 => "F21",
+            KeyCode::F22 => "F22",
+            KeyCode::F23 => "F23",
+            KeyCode::F24 => "F24",
+            KeyCode::Up => "Up",
+            KeyCode::Down => "Down",
+            KeyCode::Left => "Left",
+            KeyCode::Right => "Right",
+            KeyCode::Home => "Home",
+            KeyCode::End => "End",
+            KeyCode::PageUp => "PageUp",
+            KeyCode::PageDown => "PageDown",
+            KeyCode::Insert => "Insert",
+            KeyCode::Nop => "Nop",
+            KeyCode::Plug => "Plug",
+            KeyCode::K0 => "k0",
+            KeyCode::K1 => "k1",
+            KeyCode::K2 => "k2",
+            KeyCode::K3 => "k3",
+            KeyCode::K4 => "k4",
+            KeyCode::K5 => "k5",
+            KeyCode::K6 => "k6",
+            KeyCode::K7 => "k7",
+            KeyCode::K8 => "k8",
+            KeyCode::K9 => "k9",
+            KeyCode::KeypadPlus => "kPlus",
+            KeyCode::KeypadMinus => "kMinus",
+            KeyCode::KeypadMultiply => "kMultiply",
+            KeyCode::KeypadDivide => "kDivide",
+            KeyCode::KeypadEnter => "kEnter",
+            KeyCode::BackTab => "BackTab",
+            KeyCode::LineFeed => "NL",
+            _ => return None,
+        };
+
+        Some(name)
+    }
+
+    pub(crate) fn is_pseudo(&self) -> bool {
+        matches!(self, KeyCode::Nop | KeyCode::Plug)
+    }
+
+    pub(crate) fn is_mouse(&self) -> bool {
+        matches!(
+            self,
+            KeyCode::LeftMouse
+                | KeyCode::MiddleMouse
+                | KeyCode::RightMouse
+                | KeyCode::ScrollWheelUp
+                | KeyCode::ScrollWheelDown
+        )
+    }
+
+    pub(crate) fn from_function(n: u8) -> Option<KeyCode> {
+        if !(1..=24).contains(&n) {
+            return None;
         }
 
-        unsafe { std::mem::transmute(ascii) }
+        KeyCode::from_repr(KeyCode::F1 as u8 + n - 1)
+    }
+
+    pub(crate) fn is_function(&self) -> bool {
+        (KeyCode::F1 as u8..=KeyCode::F24 as u8).contains(&(*self as u8))
+    }
+
+    pub(crate) fn is_keypad_digit(&self) -> bool {
+        (KeyCode::K0 as u8..=KeyCode::K9 as u8).contains(&(*self as u8))
     }
 
     pub(crate) fn as_ascii(&self) -> char {
-        std::char::from_u32(*self as u32).unwrap()
+        core::char::from_u32(*self as u32).unwrap()
+    }
+}
+
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b = b.as_bytes();
+    let mut row = (0..=b.len()).collect::<Vec<_>>();
+
+    for (i, ca) in a.bytes().enumerate() {
+        let mut prev = row[0];
+
+        row[0] = i + 1;
+
+        for (j, cb) in b.iter().enumerate() {
+            let cost = usize::from(!ca.eq_ignore_ascii_case(cb));
+            let next = (prev + cost).min(row[j] + 1).min(row[j + 1] + 1);
+
+            prev = row[j + 1];
+            row[j + 1] = next;
+        }
     }
+
+    row[b.len()]
 }
@@ -0,0 +1,117 @@
+use crate::{Error, ErrorKind, Keymap};
+
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+/// [Keymap] with the leading count, e.g. `3j`.
+///
+/// `0` can't start the count, it is the key as vim does (the column 0).
+///
+/// # Example
+///
+/// ```
+/// use viks::{CountedKeymap, Keymap};
+///
+/// # fn main() {
+/// let counted = CountedKeymap::new("3j").unwrap();
+///
+/// assert_eq!(counted.count(), Some(3));
+/// assert_eq!(counted.keymap(), &Keymap::new("j").unwrap());
+/// # }
+/// ```
+pub struct CountedKeymap {
+    count: Option<u32>,
+    keymap: Keymap,
+}
+
+impl CountedKeymap {
+    /// Create new CountedKeymap.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use viks::{CountedKeymap, Keymap};
+    ///
+    /// # fn main() {
+    /// let counted = CountedKeymap::new("d2w").unwrap();
+    ///
+    /// assert_eq!(counted.count(), None);
+    /// assert_eq!(counted.keymap(), &Keymap::new("d2w").unwrap());
+    /// # }
+    /// ```
+    ///
+    /// # Error
+    ///
+    /// Returns an error if the count overflows `u32`, or the rest is not available as [Keymap].
+    pub fn new(s: &str) -> crate::Result<Self> {
+        let len = if s.starts_with(|c: char| matches!(c, '1'..='9')) {
+            s.find(|c: char| !c.is_ascii_digit()).unwrap_or(s.len())
+        } else {
+            0
+        };
+
+        let count = if len == 0 {
+            None
+        } else {
+            let Ok(count) = s[..len].parse::<u32>() else {
+                return Err(Error::new(ErrorKind::InvalidFormat, s).with_offset(0));
+            };
+
+            Some(count)
+        };
+
+        let keymap = Keymap::new(&s[len..]).map_err(|e| match e.offset() {
+            Some(offset) => e.with_offset(offset + len),
+            None => e,
+        })?;
+
+        Ok(CountedKeymap { count, keymap })
+    }
+
+    /// Get the count.
+    pub fn count(&self) -> Option<u32> {
+        self.count
+    }
+
+    /// Get the keymap after the count.
+    pub fn keymap(&self) -> &Keymap {
+        &self.keymap
+    }
+
+    /// Split into the count and the keymap.
+    pub fn into_parts(self) -> (Option<u32>, Keymap) {
+        (self.count, self.keymap)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    #[test]
+    fn counted_keymap() {
+        let counted1 = CountedKeymap::new("3j").unwrap();
+        let counted2 = CountedKeymap::new("2dd").unwrap();
+        let counted3 = CountedKeymap::new("d2w").unwrap();
+        let counted4 = CountedKeymap::new("0").unwrap();
+        let counted5 = CountedKeymap::new("<c-6>").unwrap();
+        let counted6 = CountedKeymap::new("105G").unwrap();
+
+        assert_eq!(
+            counted1.clone().into_parts(),
+            (Some(3), Keymap::new("j").unwrap())
+        );
+        assert_eq!(counted2.into_parts(), (Some(2), Keymap::new("dd").unwrap()));
+        assert_eq!(counted3.into_parts(), (None, Keymap::new("d2w").unwrap()));
+        assert_eq!(counted4.into_parts(), (None, Keymap::new("0").unwrap()));
+        assert_eq!(counted5.into_parts(), (None, Keymap::new("<c-6>").unwrap()));
+        assert_eq!(
+            counted6.into_parts(),
+            (Some(105), Keymap::new("G").unwrap())
+        );
+    }
+
+    #[test]
+    fn invalid_counted_keymap() {
+        assert!(CountedKeymap::new("99999999999j").is_err());
+        assert_eq!(CountedKeymap::new("3<BOO>").unwrap_err().offset(), Some(1));
+    }
+}
@@ -0,0 +1,266 @@
+//! Crossterm implemetations.
+//!
+//! This implements are enable with `crossterm` feature.  
+//! ```sh
+//! viks = { version = "*", features = ["crossterm"] }
+//! ```
+//!
+//! Alphabetic is normalized to the lowercase char with the shift, and
+//! the shift of other printable chars is ignored because the char is already shifted.
+//! The non-ascii char is the unicode key, see [ParseOptions::unicode](crate::ParseOptions::unicode).
+//!
+//! The `Release` event is [ErrorKind::Released](crate::ErrorKind::Released), since the key is already
+//! taken from its `Press`, e.g. on Windows or with the kitty keyboard enhancement.
+//! The `Repeat` event is the same key as the `Press`, the held key is repeated.
+//!
+//! ```
+//! use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+//! use viks::Key;
+//!
+//! # fn main() {
+//! let event = KeyEvent::new(KeyCode::Char('A'), KeyModifiers::SHIFT);
+//!
+//! assert_eq!(Key::try_from(event).unwrap(), Key::new("A").unwrap());
+//! # }
+//! ```
+
+use crossterm::event::{
+    KeyCode as CtCode, KeyEvent, KeyEventKind as CtKind, KeyModifiers as CtModifiers,
+};
+
+use crate::{Code, Error, ErrorKind, Key, KeyCode, KeyModifiers, Leader, Payload};
+
+impl TryFrom<KeyEvent> for Key {
+    type Error = Error;
+
+    fn try_from(value: KeyEvent) -> Result<Self, Self::Error> {
+        let unsupported = || Error::new(ErrorKind::UnknownKey, &format!("{:?}", value.code));
+
+        if value.kind == CtKind::Release {
+            return Err(Error::new(ErrorKind::Released, &format!("{value:?}")));
+        }
+
+        let mut modifiers = KeyModifiers::NONE;
+
+        if value.modifiers.contains(CtModifiers::CONTROL) {
+            modifiers = modifiers | KeyModifiers::CONTROL;
+        }
+
+        if value.modifiers.contains(CtModifiers::ALT) {
+            modifiers = modifiers | KeyModifiers::ALT;
+        }
+
+        if value.modifiers.contains(CtModifiers::SHIFT) {
+            modifiers = modifiers | KeyModifiers::SHIFT;
+        }
+
+        if value
+            .modifiers
+            .intersects(CtModifiers::SUPER | CtModifiers::HYPER | CtModifiers::META)
+        {
+            return Err(Error::new(
+                ErrorKind::InvalidModifier,
+                &format!("{:?}", value.modifiers),
+            ));
+        }
+
+        let code = match value.code {
+            CtCode::Char(c) if c.is_ascii_alphabetic() => {
+                if c.is_ascii_uppercase() {
+                    modifiers = modifiers | KeyModifiers::SHIFT;
+                }
+
+                KeyCode::from_ascii(c.to_ascii_uppercase() as u8).ok_or_else(unsupported)?
+            }
+            CtCode::Char(c) if !c.is_ascii() => {
+                modifiers = KeyModifiers(modifiers.0 & !KeyModifiers::SHIFT.0);
+
+                return Ok(Key::from_unicode(c).with_modifiers(modifiers));
+            }
+            CtCode::Char(c) if matches!(c, ' '..='~') => {
+                modifiers = KeyModifiers(modifiers.0 & !KeyModifiers::SHIFT.0);
+
+                KeyCode::from_ascii(c as u8).ok_or_else(unsupported)?
+            }
+            CtCode::Backspace => KeyCode::Backspace,
+            CtCode::Enter => KeyCode::Enter,
+            CtCode::Left => KeyCode::Left,
+            CtCode::Right => KeyCode::Right,
+            CtCode::Up => KeyCode::Up,
+            CtCode::Down => KeyCode::Down,
+            CtCode::Home => KeyCode::Home,
+            CtCode::End => KeyCode::End,
+            CtCode::PageUp => KeyCode::PageUp,
+            CtCode::PageDown => KeyCode::PageDown,
+            CtCode::Tab => KeyCode::Tab,
+            CtCode::BackTab => KeyCode::BackTab,
+            CtCode::Delete => KeyCode::Delete,
+            CtCode::Insert => KeyCode::Insert,
+            CtCode::F(n) => KeyCode::from_function(n).ok_or_else(unsupported)?,
+            CtCode::Esc => KeyCode::Esc,
+            _ => return Err(unsupported()),
+        };
+
+        let key = Key {
+            code,
+            modifiers: KeyModifiers::NONE,
+            payload: Payload::NONE,
+            leader: Leader::No,
+        };
+
+        Ok(key.with_modifiers(modifiers))
+    }
+}
+
+impl TryFrom<Key> for KeyEvent {
+    type Error = Error;
+
+    fn try_from(value: Key) -> Result<Self, Self::Error> {
+        let code = match value.code() {
+            Code::Char(c) if value.is_alpha() && value.modifiers.is_shift() => {
+                CtCode::Char(c.to_ascii_uppercase())
+            }
+            Code::Char(c) => CtCode::Char(c),
+            Code::Enter => CtCode::Enter,
+            Code::Tab => CtCode::Tab,
+            Code::BackTab => CtCode::BackTab,
+            Code::Esc => CtCode::Esc,
+            Code::Backspace => CtCode::Backspace,
+            Code::Delete => CtCode::Delete,
+            Code::Up => CtCode::Up,
+            Code::Down => CtCode::Down,
+            Code::Left => CtCode::Left,
+            Code::Right => CtCode::Right,
+            Code::Home => CtCode::Home,
+            Code::End => CtCode::End,
+            Code::PageUp => CtCode::PageUp,
+            Code::PageDown => CtCode::PageDown,
+            Code::Insert => CtCode::Insert,
+            Code::F(n) => CtCode::F(n),
+            _ => {
+                return Err(Error::new(ErrorKind::UnknownKey, &value.to_string()));
+            }
+        };
+
+        let mut modifiers = CtModifiers::NONE;
+
+        if value.modifiers.is_ctrl() {
+            modifiers |= CtModifiers::CONTROL;
+        }
+
+        if value.modifiers.is_alt() {
+            modifiers |= CtModifiers::ALT;
+        }
+
+        if value.modifiers.is_shift() || value.code == KeyCode::BackTab {
+            modifiers |= CtModifiers::SHIFT;
+        }
+
+        Ok(KeyEvent::new(code, modifiers))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crossterm::event::{
+        KeyCode as CtCode, KeyEvent, KeyEventKind as CtKind, KeyModifiers as CtModifiers,
+        ModifierKeyCode,
+    };
+
+    use crate::*;
+
+    fn event(code: CtCode, modifiers: CtModifiers) -> KeyEvent {
+        KeyEvent::new(code, modifiers)
+    }
+
+    #[test]
+    fn from_event() {
+        let unicode = ParseOptions {
+            unicode: true,
+            ..Default::default()
+        };
+        let cases = [
+            (event(CtCode::Char('a'), CtModifiers::NONE), "a"),
+            (event(CtCode::Char('A'), CtModifiers::SHIFT), "A"),
+            (event(CtCode::Char('A'), CtModifiers::NONE), "A"),
+            (event(CtCode::Char('a'), CtModifiers::SHIFT), "A"),
+            (event(CtCode::Char('!'), CtModifiers::SHIFT), "!"),
+            (event(CtCode::Char(' '), CtModifiers::NONE), "<space>"),
+            (event(CtCode::Char('<'), CtModifiers::NONE), "<lt>"),
+            (event(CtCode::Char('w'), CtModifiers::CONTROL), "<c-w>"),
+            (
+                event(CtCode::Char('W'), CtModifiers::CONTROL | CtModifiers::SHIFT),
+                "<c-s-w>",
+            ),
+            (event(CtCode::Enter, CtModifiers::ALT), "<a-cr>"),
+            (event(CtCode::F(5), CtModifiers::NONE), "<F5>"),
+            (event(CtCode::Up, CtModifiers::CONTROL), "<c-up>"),
+            (event(CtCode::BackTab, CtModifiers::SHIFT), "<s-tab>"),
+            (event(CtCode::BackTab, CtModifiers::NONE), "<BackTab>"),
+            (event(CtCode::Tab, CtModifiers::SHIFT), "<BackTab>"),
+        ];
+
+        for (event, expected) in cases {
+            assert_eq!(Key::try_from(event).unwrap(), Key::new(expected).unwrap());
+        }
+
+        assert_eq!(
+            Key::try_from(event(CtCode::Char('é'), CtModifiers::NONE))
+                .unwrap()
+                .code(),
+            Code::Char('é')
+        );
+        assert_eq!(
+            Key::try_from(event(CtCode::Char('É'), CtModifiers::SHIFT)).unwrap(),
+            Key::new_with_options("É", &unicode).unwrap()
+        );
+        assert_eq!(
+            Key::try_from(event(CtCode::Char('é'), CtModifiers::ALT)).unwrap(),
+            Key::new_with_options("<a-é>", &unicode).unwrap()
+        );
+        assert!(Key::try_from(event(CtCode::CapsLock, CtModifiers::NONE)).is_err());
+        assert!(
+            Key::try_from(event(
+                CtCode::Modifier(ModifierKeyCode::LeftControl),
+                CtModifiers::CONTROL
+            ))
+            .is_err()
+        );
+        assert!(Key::try_from(event(CtCode::Char('a'), CtModifiers::SUPER)).is_err());
+    }
+
+    #[test]
+    fn event_kinds() {
+        let press = event(CtCode::Char('j'), CtModifiers::NONE);
+        let repeat = KeyEvent::new_with_kind(CtCode::Char('j'), CtModifiers::NONE, CtKind::Repeat);
+        let release =
+            KeyEvent::new_with_kind(CtCode::Char('j'), CtModifiers::NONE, CtKind::Release);
+
+        assert_eq!(Key::try_from(press).unwrap(), Key::new("j").unwrap());
+        assert_eq!(Key::try_from(repeat).unwrap(), Key::new("j").unwrap());
+        assert_eq!(
+            Key::try_from(release).unwrap_err().kind(),
+            ErrorKind::Released
+        );
+    }
+
+    #[test]
+    fn round_trip() {
+        let keymap = Keymap::new_with_options(
+            "aA;<c-w><c-s-w><a-cr><F12><c-Up><Home><space><lt><BackTab>é<c-あ>",
+            &ParseOptions {
+                unicode: true,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+        for key in &keymap {
+            let event = KeyEvent::try_from(*key).unwrap();
+
+            assert_eq!(Key::try_from(event).unwrap(), *key, "{key}");
+        }
+
+        assert!(KeyEvent::try_from(Key::new("<LeftMouse>").unwrap()).is_err());
+    }
+}
@@ -1,7 +1,58 @@
-type Format = String;
-type Cause = String;
+use alloc::string::{String, ToString};
 
-#[derive(Debug)]
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+#[non_exhaustive]
+/// Kind of [Error].
+///
+/// # Example
+///
+/// ```
+/// use viks::{ErrorKind, Key, Keymap};
+///
+/// # fn main() {
+/// assert_eq!(Key::new("").unwrap_err().kind(), ErrorKind::Empty);
+/// assert_eq!(Key::new("<BOO>").unwrap_err().kind(), ErrorKind::UnknownKey);
+/// assert_eq!(Keymap::new("<leader").unwrap_err().kind(), ErrorKind::UnclosedTag);
+/// # }
+/// ```
+pub enum ErrorKind {
+    /// The format is empty.
+    Empty,
+    /// The format contains non-ascii chars.
+    NotAscii,
+    /// The format is not a single char or not surrounded `<>`.
+    InvalidFormat,
+    /// The char or the key name is not available.
+    UnknownKey,
+    /// The `<` is not closed.
+    UnclosedTag,
+    /// The `<` is in the other `<...>`, e.g. `<<c-a>`.
+    NestedTag,
+    /// The modifier is not available.
+    InvalidModifier,
+    /// The modifier has no key, e.g. `<c->`.
+    MissingKey,
+    /// The input event is the key release, not the key press.
+    Released,
+}
+
+impl ErrorKind {
+    fn cause(&self) -> &'static str {
+        match self {
+            ErrorKind::Empty => "format is empty",
+            ErrorKind::NotAscii => "format is not ascii",
+            ErrorKind::InvalidFormat => "unsupported key format",
+            ErrorKind::UnknownKey => "unknown key",
+            ErrorKind::UnclosedTag => "tag is not closed",
+            ErrorKind::NestedTag => "tag is nested",
+            ErrorKind::InvalidModifier => "unsupported modifier",
+            ErrorKind::MissingKey => "modifier has no key",
+            ErrorKind::Released => "key is released",
+        }
+    }
+}
+
+#[derive(Clone, PartialEq, Eq, Debug)]
 /// viks error type.
 ///
 /// # Example
@@ -15,17 +66,67 @@ type Cause = String;
 ///     eprintln!("incorrect syntax: {}", err);
 /// }
 /// ```
-pub struct Error(Format, Cause);
+pub struct Error {
+    kind: ErrorKind,
+    format: String,
+    offset: Option<usize>,
+    index: Option<usize>,
+    suggestion: Option<String>,
+}
 
-impl std::fmt::Display for Error {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}", self.cause())
+impl core::fmt::Display for Error {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{}", self.cause())?;
+
+        if let Some(suggestion) = self.suggestion() {
+            write!(f, ", did you mean `<{suggestion}>`?")?;
+        }
+
+        Ok(())
     }
 }
 
 impl Error {
-    pub(crate) fn new(format: &str, cause: &str) -> Self {
-        Self(format.to_string(), cause.to_string())
+    pub(crate) fn new(kind: ErrorKind, format: &str) -> Self {
+        Self {
+            kind,
+            format: format.to_string(),
+            offset: None,
+            index: None,
+            suggestion: None,
+        }
+    }
+
+    pub(crate) fn with_offset(mut self, offset: usize) -> Self {
+        self.offset = Some(offset);
+        self
+    }
+
+    pub(crate) fn with_index(mut self, index: usize) -> Self {
+        self.index = Some(index);
+        self
+    }
+
+    pub(crate) fn with_suggestion(mut self, suggestion: &str) -> Self {
+        self.suggestion = Some(suggestion.to_string());
+        self
+    }
+
+    /// Get kind of Error.
+    ///
+    /// # Example
+    /// ```
+    /// # use viks::{ErrorKind, Key};
+    /// # fn main() {
+    /// let key = Key::new("<ES");
+    ///
+    /// if let Err(e) = key {
+    ///     assert_eq!(e.kind(), ErrorKind::InvalidFormat);
+    /// }
+    /// # }
+    /// ```
+    pub fn kind(&self) -> ErrorKind {
+        self.kind
     }
 
     /// Get format of Error.
@@ -42,7 +143,7 @@ impl Error {
     /// # }
     /// ```
     pub fn format(&self) -> &str {
-        &self.0
+        &self.format
     }
 
     /// Get error cause.
@@ -59,11 +160,64 @@ impl Error {
     /// # }
     /// ```
     pub fn cause(&self) -> &str {
-        &self.1
+        self.kind.cause()
+    }
+
+    /// Get the byte offset of the failure in the [Keymap](crate::Keymap) input.
+    ///
+    /// # Example
+    /// ```
+    /// # use viks::Keymap;
+    /// # fn main() {
+    /// let keymap = Keymap::new("ab<leader");
+    ///
+    /// if let Err(e) = keymap {
+    ///     assert_eq!(e.offset(), Some(2));
+    /// }
+    /// # }
+    /// ```
+    pub fn offset(&self) -> Option<usize> {
+        self.offset
+    }
+
+    /// Get the index of the failed key in the [Keymap](crate::Keymap) input.
+    ///
+    /// # Example
+    /// ```
+    /// # use viks::Keymap;
+    /// # fn main() {
+    /// let keymap = Keymap::new("ab<BOO>cd");
+    ///
+    /// if let Err(e) = keymap {
+    ///     assert_eq!(e.index(), Some(2));
+    ///     assert_eq!(e.offset(), Some(2));
+    /// }
+    /// # }
+    /// ```
+    pub fn index(&self) -> Option<usize> {
+        self.index
+    }
+
+    /// Get the closest available key name, if the key name is unknown.
+    ///
+    /// # Example
+    /// ```
+    /// # use viks::Key;
+    /// # fn main() {
+    /// let key = Key::new("<entre>");
+    ///
+    /// if let Err(e) = key {
+    ///     assert_eq!(e.suggestion(), Some("enter"));
+    /// }
+    /// # }
+    /// ```
+    pub fn suggestion(&self) -> Option<&str> {
+        self.suggestion.as_deref()
     }
 }
 
+#[cfg(feature = "std")]
 impl std::error::Error for Error {}
 
 /// viks result alias.
-pub type Result<T> = std::result::Result<T, Error>;
+pub type Result<T> = core::result::Result<T, Error>;
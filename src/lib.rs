@@ -1,3 +1,4 @@
+#![cfg_attr(not(feature = "std"), no_std)]
 #![warn(missing_docs)]
 #![allow(dead_code)]
 
@@ -39,21 +40,62 @@
 //! }
 //! # }
 //! ```
+//!
+//! ## `no_std`
+//!
+//! The default `std` feature can be disabled to use the crate with only `alloc`.
+
+extern crate alloc;
 
 mod code;
+mod count;
 mod error;
 mod modifier;
+mod options;
+mod parser;
+mod raw;
+mod set;
+mod tag;
+mod token;
+mod trie;
+
+pub mod noremap;
 
 #[cfg(feature = "serde")]
 pub mod serde_impl;
 
+#[cfg(feature = "crossterm")]
+pub mod crossterm_impl;
+
+#[cfg(feature = "termion")]
+pub mod termion_impl;
+
+pub use code::Code;
 use code::KeyCode;
-pub use error::{Error, Result};
-use modifier::{KeyModifier, KeyModifiers};
+pub use count::CountedKeymap;
+pub use error::{Error, ErrorKind, Result};
+pub use modifier::{KeyModifiers, Modifier};
+pub use options::ParseOptions;
+pub use parser::KeymapParser;
+pub use raw::RawKeymap;
+pub use set::KeymapSet;
+use tag::Tag;
+pub use token::KeyToken;
+pub use trie::{KeymapTrie, Step};
 
-#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+use alloc::collections::BTreeSet;
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec;
+use alloc::vec::Vec;
+use core::ops::Range;
+
+#[derive(Clone, Copy)]
 /// Minimum unit to use for parsing.
 ///
+/// Every spelling of the same key is normalized while parsing, so `Eq`, `Hash`
+/// and `Ord` agree with each other, e.g. `A` and `<s-a>` are the same key.
+///
 /// ## Example
 /// ```
 /// use viks::Key;
@@ -68,9 +110,100 @@ use modifier::{KeyModifier, KeyModifiers};
 pub struct Key {
     code: KeyCode,
     modifiers: KeyModifiers,
+    payload: Payload,
+    // written as `<leader>`, only for the `Display` and the `describe`
+    leader: Leader,
+}
+
+/// How the key was written as `<leader>`.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+enum Leader {
+    /// Not written as `<leader>`.
+    No,
+    /// Written as `<leader>`, and displayed as the resolved key.
+    Resolved,
+    /// Written as `<leader>`, and displayed as `<leader>`, see [ParseOptions::preserve_leader].
+    Preserved,
+}
+
+/// The char of the unicode key or the tag of the unknown key, in the same 4 bytes.
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+struct Payload(u32);
+
+impl Payload {
+    /// Neither the char nor the tag.
+    const NONE: Payload = Payload(u32::MAX);
+    // the tags are after the chars
+    const TAG_START: u32 = char::MAX as u32 + 1;
+
+    const fn from_char(c: char) -> Payload {
+        Payload(c as u32)
+    }
+
+    fn from_tag(tag: Tag) -> Payload {
+        Payload(Payload::TAG_START + tag.0)
+    }
+
+    fn char(self) -> Option<char> {
+        char::from_u32(self.0)
+    }
+
+    fn tag(self) -> Option<Tag> {
+        (Payload::TAG_START..u32::MAX)
+            .contains(&self.0)
+            .then(|| Tag(self.0 - Payload::TAG_START))
+    }
+}
+
+/// The tags are ordered by the text, not by the interned order.
+impl Ord for Payload {
+    fn cmp(&self, other: &Self) -> core::cmp::Ordering {
+        match (self.tag(), other.tag()) {
+            (Some(tag), Some(other)) => tag.as_str().cmp(other.as_str()),
+            _ => self.0.cmp(&other.0),
+        }
+    }
+}
+
+impl PartialOrd for Payload {
+    fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
 }
 
 impl Key {
+    /// `<CR>` key.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use viks::Key;
+    ///
+    /// # fn main() {
+    /// assert_eq!(Key::ENTER, Key::new("<CR>").unwrap());
+    /// # }
+    /// ```
+    pub const ENTER: Key = Key::named(KeyCode::Enter);
+    /// `<Esc>` key.
+    pub const ESC: Key = Key::named(KeyCode::Esc);
+    /// `<Tab>` key.
+    pub const TAB: Key = Key::named(KeyCode::Tab);
+    /// `<Space>` key.
+    pub const SPACE: Key = Key::named(KeyCode::Space);
+    /// `<BS>` key.
+    pub const BACKSPACE: Key = Key::named(KeyCode::Backspace);
+    /// `<Del>` key.
+    pub const DELETE: Key = Key::named(KeyCode::Delete);
+
+    const fn named(code: KeyCode) -> Key {
+        Key {
+            code,
+            modifiers: KeyModifiers::NONE,
+            payload: Payload::NONE,
+            leader: Leader::No,
+        }
+    }
+
     /// Create new key.
     ///
     /// ## Example
@@ -80,8 +213,10 @@ impl Key {
     /// # fn main() {
     /// let shift_a_key = Key::new("A").unwrap();
     /// let shift_a_key_alt = Key::new("<s-a>").unwrap();
+    /// let shift_a_key_owned = Key::new(String::from("A")).unwrap();
     ///
     /// assert_eq!(shift_a_key, shift_a_key_alt);
+    /// assert_eq!(shift_a_key, shift_a_key_owned);
     /// # }
     /// ```
     ///
@@ -100,384 +235,3469 @@ impl Key {
     ///   - Delete
     /// - not surrounded <> if len > 1
     /// - surrounded <> but not available
-    pub fn new(tag: &str) -> self::Result<Self> {
-        use std::str::FromStr;
+    /// - modifier is not one of `s`/`shift`, `c`/`ctrl`/`control`, `a`/`alt`
+    /// - modifier is empty, e.g. `<-a>`
+    /// - modifier has no key, e.g. `<c->`
+    ///
+    /// Modifiers can be combined, e.g. `<c-s-a>`, and are available for every named key,
+    /// e.g. `<c-space>`, `<a-esc>` or `<c-bs>`.
+    ///
+    /// The ascii key can be specified by the number, e.g. `<Char-0x41>` or `<Char-97>`.
+    ///
+    /// Shift-Tab is the distinct key, `<s-tab>` is normalized to `<BackTab>`.
+    ///
+    /// The hyphen is available as `<->` and with the modifier, e.g. `<c-->`.
+    ///
+    /// The unrecognized tag is kept as [Code::Unknown] with [ParseOptions::strict] disabled.
+    pub fn new(tag: impl AsRef<str>) -> self::Result<Self> {
+        Key::new_with_options(tag.as_ref(), &ParseOptions::default())
+    }
 
-        if !tag.is_ascii() {
-            return Err(Error::new(tag, "unsupported key format"));
+    /// Create new key with [ParseOptions].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use viks::{Key, ParseOptions};
+    ///
+    /// # fn main() {
+    /// let options = ParseOptions {
+    ///     leader: Key::new(",").unwrap(),
+    ///     ..Default::default()
+    /// };
+    ///
+    /// assert_eq!(
+    ///     Key::new_with_options("<leader>", &options).unwrap(),
+    ///     Key::new(",").unwrap()
+    /// );
+    /// # }
+    /// ```
+    ///
+    /// # Error
+    ///
+    /// Same as [Key::new].
+    pub fn new_with_options(tag: &str, options: &ParseOptions) -> self::Result<Self> {
+        use core::str::FromStr;
+
+        if !tag.is_ascii() && !options.unicode {
+            return Err(Error::new(ErrorKind::NotAscii, tag));
         }
 
         if tag.is_empty() {
-            return Err(Error::new(tag, "format is empty"));
+            return Err(Error::new(ErrorKind::Empty, tag));
         }
 
-        if tag.len() == 1 {
-            let Ok(tag_char) = char::from_str(tag) else {
-                return Err(Error::new(tag, "unsupported key format"));
-            };
+        if let Ok(tag_char) = char::from_str(tag) {
+            return Key::from_char(tag_char, options);
+        }
 
-            let modifier = if tag_char.is_ascii_uppercase() {
-                KeyModifier::Shift
-            } else {
-                KeyModifier::None
-            };
+        let is_special = tag.starts_with("<") && tag.ends_with(">");
+
+        if !is_special || tag.len() == 2 {
+            return Err(Error::new(ErrorKind::InvalidFormat, tag));
+        }
 
-            let tag_uppercase = tag_char.to_ascii_uppercase();
+        let mut modifiers = KeyModifiers::NONE;
+        let mut base = &tag[1..tag.len() - 1];
+
+        while let Some((prefix, rest)) = base.split_once('-') {
+            if prefix.is_empty() {
+                // `<->` is the hyphen itself
+                if rest.is_empty() {
+                    break;
+                }
 
-            let code = match tag_uppercase {
-                'A'..='Z' => KeyCode::from_ascii(tag_uppercase as u8),
-                '!' | '"' | '#' | '$' | '%' | '&' | '\'' | '(' | ')' | '*' | '+' | '?' | '_'
-                | '`' | '|' | '~' | '{' | '}' | '-' | '[' | ']' | ',' | '.' | '/' | ':' | ';'
-                | '>' | '=' | '@' | '\\' | '^' => KeyCode::from_ascii(tag_uppercase as u8),
+                return Err(Error::new(ErrorKind::InvalidModifier, tag));
+            }
 
-                tag_char if tag_char.is_ascii_digit() => KeyCode::from_ascii(tag_char as u8),
+            if prefix.eq_ignore_ascii_case("char") {
+                return Key::from_char_code(rest)
+                    .map(|key| key.with_modifiers(modifiers))
+                    .map_err(|kind| Error::new(kind, tag));
+            }
 
-                _ => return Err(Error::new(tag, "unsupported key format")),
+            let Some(modifier) = KeyModifiers::from_name(prefix) else {
+                return Err(Error::new(ErrorKind::InvalidModifier, tag));
             };
 
-            return Ok(Key {
-                code,
-                modifiers: KeyModifiers(modifier),
-            });
-        }
+            if rest.is_empty() {
+                return Err(Error::new(ErrorKind::MissingKey, tag));
+            }
 
-        let is_special = tag.starts_with("<") && tag.ends_with(">");
+            modifiers = modifiers | modifier;
+            base = rest;
+        }
 
-        if !is_special || tag.len() == 2 {
-            return Err(Error::new(tag, "unsupported key format"));
+        if base.chars().count() == 1 {
+            return Ok(Key::new_with_options(base, options)?.with_modifiers(modifiers));
         }
 
-        let is_modded = tag.chars().nth(2).is_some_and(|c| c == '-');
-        let base = if is_modded {
-            &tag[3..tag.len() - 1]
-        } else {
-            &tag[1..tag.len() - 1]
-        };
-        let modifier = if is_modded {
-            match tag.chars().nth(1).map(|c| c.to_ascii_lowercase()) {
-                Some('a') => KeyModifier::Alt,
-                Some('c') => KeyModifier::Control,
-                Some('s') => KeyModifier::Shift,
-                _ => KeyModifier::None,
-            }
-        } else {
-            KeyModifier::None
-        };
+        if let Some((_, key)) = options
+            .aliases
+            .iter()
+            .find(|(name, _)| base.eq_ignore_ascii_case(name))
+        {
+            return Ok(key.with_modifiers(modifiers));
+        }
 
-        if base.len() == 1 {
-            let mut key = Key::new(base)?;
+        if base.eq_ignore_ascii_case("leader") {
+            let key = options.leader.with_modifiers(modifiers);
 
-            key.modifiers = KeyModifiers(key.modifiers.0 | modifier);
+            let leader = match options.preserve_leader {
+                true => Leader::Preserved,
+                false => Leader::Resolved,
+            };
 
-            return Ok(key);
+            return Ok(Key { leader, ..key });
         }
 
-        let code = match base.to_lowercase().as_str() {
-            "enter" | "cr" => KeyCode::Enter,
-            "tab" => KeyCode::Tab,
-            "esc" => KeyCode::Esc,
-            "leader" | "space" => KeyCode::Space,
-            "bs" => KeyCode::Backspace,
-            "del" => KeyCode::Delete,
-            "lt" => KeyCode::LessThanSign,
-            _ => return Err(Error::new(tag, "unsupported key format")),
+        let Some(code) = KeyCode::from_name(base) else {
+            if !options.strict {
+                return Ok(Key::unknown(base).with_modifiers(modifiers));
+            }
+
+            let err = Error::new(ErrorKind::UnknownKey, tag);
+
+            return Err(match KeyCode::suggest_name(base) {
+                Some(suggestion) => err.with_suggestion(suggestion),
+                None => err,
+            });
         };
 
-        Ok(Key {
+        let key = Key {
             code,
-            modifiers: KeyModifiers(modifier),
-        })
+            modifiers: KeyModifiers::NONE,
+            payload: Payload::NONE,
+            leader: Leader::No,
+        };
+
+        Ok(key.with_modifiers(modifiers))
     }
 
-    /// Returns `true` if this `Key` is the alphabetic.
+    /// Create new key from the ascii bytes of the tag, e.g. `b"<c-a>"`.
     ///
     /// # Example
     /// ```
     /// # use viks::Key;
     /// # fn main() {
-    /// assert!(Key::new("a").unwrap().is_alpha());
-    /// assert!(Key::new("<c-a>").unwrap().is_alpha());
-    /// assert!(Key::new("A").unwrap().is_alpha());
-    /// assert!(!Key::new("~").unwrap().is_alpha());
-    /// assert!(!Key::new("1").unwrap().is_alpha());
-    /// assert!(!Key::new(":").unwrap().is_alpha());
+    /// assert_eq!(Key::new_bytes(b"<c-a>").unwrap(), Key::new("<c-a>").unwrap());
+    /// assert!(Key::new_bytes(b"\xff").is_err());
     /// # }
     /// ```
-    pub fn is_alpha(&self) -> bool {
-        self.code.as_ascii().is_uppercase()
+    ///
+    /// # Error
+    ///
+    /// Same as [Key::new].
+    pub fn new_bytes(tag: &[u8]) -> self::Result<Self> {
+        if !tag.is_ascii() {
+            return Err(Error::new(
+                ErrorKind::NotAscii,
+                &String::from_utf8_lossy(tag),
+            ));
+        }
+
+        Key::new(core::str::from_utf8(tag).expect("ascii is valid utf-8"))
     }
 
-    /// Returns `true` if this `Key` code in '0'..='9'.
+    /// Check that the tag is available as Key.
     ///
     /// # Example
     /// ```
     /// # use viks::Key;
     /// # fn main() {
-    /// assert!(!Key::new("a").unwrap().is_digit());
-    /// assert!(!Key::new("<c-a>").unwrap().is_digit());
-    /// assert!(!Key::new("A").unwrap().is_digit());
-    /// assert!(!Key::new("~").unwrap().is_digit());
-    /// assert!(Key::new("1").unwrap().is_digit());
-    /// assert!(Key::new("9").unwrap().is_digit());
-    /// assert!(!Key::new(":").unwrap().is_digit());
+    /// assert!(Key::validate("<c-a>").is_ok());
+    /// assert_eq!(Key::validate("<BOO>"), Key::new("<BOO>").map(|_| ()));
     /// # }
     /// ```
-    pub fn is_digit(&self) -> bool {
-        self.code.as_ascii().is_ascii_digit()
+    ///
+    /// # Error
+    ///
+    /// Returns the same error as [Key::new].
+    pub fn validate(tag: &str) -> self::Result<()> {
+        Key::new(tag).map(|_| ())
     }
-}
 
-impl std::fmt::Display for Key {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}", {
-            let is_special = matches!(
-                self.code,
-                KeyCode::Enter
-                    | KeyCode::Tab
-                    | KeyCode::Esc
-                    | KeyCode::Space
-                    | KeyCode::Backspace
-                    | KeyCode::Delete
-                    | KeyCode::LessThanSign,
-            );
-            let is_modded = self.modifiers.is_alt() || self.modifiers.is_ctrl();
-            let is_shift = self.modifiers.is_shift();
-            let is_alpha = matches!(self.code as u8, 65..=90);
-
-            let code = match &self.code {
-                KeyCode::Enter => "CR",
-                KeyCode::Tab => "TAB",
-                KeyCode::Esc => "ESC",
-                KeyCode::Space => "SPACE",
-                KeyCode::Backspace => "BS",
-                KeyCode::Delete => "DEL",
-                KeyCode::LessThanSign => "LT",
-
-                keycode if !is_shift && is_alpha => {
-                    &format!("{}", keycode.as_ascii().to_ascii_lowercase())
-                }
+    /// Create new key from the code token and the modifier flags, without the tag syntax.
+    ///
+    /// The code token is the single char, e.g. `a` or `é`, or the key name, e.g. `CR`.
+    /// The uppercase alphabetic has Shift, e.g. `A` is `<s-a>`.
+    ///
+    /// # Example
+    /// ```
+    /// # use viks::Key;
+    /// # fn main() {
+    /// assert_eq!(Key::from_parts("a", false, true, false).unwrap(), Key::new("<c-a>").unwrap());
+    /// assert_eq!(Key::from_parts("CR", false, false, true).unwrap(), Key::new("<a-cr>").unwrap());
+    /// # }
+    /// ```
+    ///
+    /// # Error
+    ///
+    /// Returns an error if the code token is not the char or the key name, e.g. `<CR>` or `c-a`
+    /// is [ErrorKind::UnknownKey].
+    pub fn from_parts(code_token: &str, shift: bool, ctrl: bool, alt: bool) -> self::Result<Self> {
+        let key = if code_token.chars().count() == 1 {
+            let options = ParseOptions {
+                unicode: true,
+                ..Default::default()
+            };
 
-                keycode => &format!("{}", keycode.as_ascii()),
+            Key::new_with_options(code_token, &options)?
+        } else {
+            let Some(code) = KeyCode::from_name(code_token) else {
+                return Err(Error::new(ErrorKind::UnknownKey, code_token));
             };
 
-            let code = if self.modifiers.is_alt() {
-                &format!("a-{code}")
-            } else if self.modifiers.is_ctrl() {
-                &format!("c-{code}")
-            } else if is_shift && !is_alpha {
-                &format!("s-{code}")
-            } else {
-                &code.to_string()
+            Key::named(code)
+        };
+
+        let flags = [
+            (shift, KeyModifiers::SHIFT),
+            (ctrl, KeyModifiers::CONTROL),
+            (alt, KeyModifiers::ALT),
+        ];
+
+        Ok(flags
+            .into_iter()
+            .filter(|(set, _)| *set)
+            .fold(key, |key, (_, modifier)| key.with_modifiers(modifier)))
+    }
+
+    /// Create new key of the single char tag without allocation.
+    pub(crate) fn from_char(c: char, options: &ParseOptions) -> self::Result<Self> {
+        let mut tag = [0; 4];
+        let tag = c.encode_utf8(&mut tag);
+
+        if !c.is_ascii() {
+            return match options.unicode {
+                true => Ok(Key::from_unicode(c)),
+                false => Err(Error::new(ErrorKind::NotAscii, tag)),
             };
+        }
 
-            if is_special || is_modded || is_shift && !is_alpha {
-                format!("<{code}>")
-            } else {
-                code.to_string()
-            }
-        })
+        Key::from_visible_char(c).ok_or_else(|| Error::new(ErrorKind::UnknownKey, tag))
     }
-}
 
-impl std::fmt::Debug for Key {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(
-            f,
-            "Key {{ code: {}, modifiers: {:#05b} }}",
-            self.code as u8, self.modifiers.0 as u8
-        )
+    /// Create new key of the unrecognized tag name, the name is kept as is.
+    fn unknown(name: &str) -> Key {
+        Key {
+            payload: Payload::from_tag(Tag::intern(name)),
+            ..Key::named(KeyCode::Unknown)
+        }
     }
-}
 
-#[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
-/// Wrapper of [Vec]<[Key]>.
-///
-/// This only has parse func, please use `as_vec` to access to inner.
-///
-/// # Example
-///
-/// ```
-/// use viks::Keymap;
-///
-/// # fn main() {
-/// let exit_map = Keymap::new("ZZ").unwrap();
-/// let exit_map_alt = Keymap::new("<s-z>Z").unwrap();
-///
-/// assert_eq!(exit_map, exit_map_alt);
-/// # }
-/// ```
-pub struct Keymap(Vec<Key>);
+    /// Create new key of the char, non-ascii char is kept as is.
+    fn from_unicode(c: char) -> Key {
+        Key {
+            code: KeyCode::Unicode,
+            modifiers: KeyModifiers::NONE,
+            payload: Payload::from_char(c),
+            leader: Leader::No,
+        }
+    }
 
-impl Keymap {
-    /// Create new Keymap.
+    /// Create new key from the byte of the raw terminal input.
     ///
-    /// # Example
+    /// | byte             | key                                |
+    /// | ---------------- | ---------------------------------- |
+    /// | `0x00`           | `<c-@>`                            |
+    /// | `0x01` .. `0x1a` | `<c-a>` .. `<c-z>`, except below   |
+    /// | `0x09`           | `<Tab>`, same byte as `<c-i>`      |
+    /// | `0x0a`           | `<NL>`, same byte as `<c-j>`       |
+    /// | `0x0d`           | `<CR>`, same byte as `<c-m>`       |
+    /// | `0x1b`           | `<Esc>`, same byte as `<c-[>`      |
+    /// | `0x1c` .. `0x1f` | `<c-\>`, `<c-]>`, `<c-^>`, `<c-_>` |
+    /// | `0x20`           | `<Space>`                          |
+    /// | `0x21` .. `0x7e` | the printable ascii                |
+    /// | `0x7f`           | `<BS>`, as the terminals send      |
     ///
-    /// ```
-    /// use viks::Keymap;
+    /// `0x08` is `<c-h>`, not `<BS>`.
     ///
+    /// # Example
+    /// ```
+    /// # use viks::Key;
     /// # fn main() {
-    /// let exit_map = Keymap::new("ZZ").unwrap();
-    /// let exit_map_alt = Keymap::new("<s-z>Z").unwrap();
-    ///
-    /// assert_eq!(exit_map, exit_map_alt);
+    /// assert_eq!(Key::from_byte(0x01).unwrap(), Key::new("<c-a>").unwrap());
+    /// assert_eq!(Key::from_byte(0x0d).unwrap(), Key::new("<cr>").unwrap());
+    /// assert_eq!(Key::from_byte(0x0a).unwrap(), Key::new("<nl>").unwrap());
+    /// assert_eq!(Key::from_byte(b'A').unwrap(), Key::new("A").unwrap());
     /// # }
     /// ```
     ///
     /// # Error
     ///
-    /// Returns an error if the tag is not closed.
-    pub fn new(s: &str) -> self::Result<Self> {
-        let mut in_tag = false;
-        let mut buf = String::new();
-        let mut keys: Vec<Key> = vec![];
+    /// Returns an error if the byte is not ascii.
+    pub fn from_byte(b: u8) -> self::Result<Self> {
+        let with_ctrl = |c: u8| {
+            Key::from_visible_char(c as char)
+                .expect("control byte is mapped to the visible char")
+                .with_ctrl()
+        };
 
-        for c in s.chars() {
-            if c == '<' {
-                in_tag = true;
-            }
+        let key = match b {
+            0x09 => Key::try_from('\t')?,
+            0x0a => Key::named(KeyCode::LineFeed),
+            0x0d => Key::try_from('\r')?,
+            0x1b => Key::try_from('\x1b')?,
+            0x7f => Key::try_from('\x08')?,
+            0x00..=0x1f => with_ctrl((b + b'@').to_ascii_lowercase()),
+            0x20..=0x7e => Key::try_from(b as char)?,
+            _ => return Err(Error::new(ErrorKind::NotAscii, &format!("{b:#04x}"))),
+        };
 
-            if in_tag {
-                buf.push(c);
-            } else {
-                keys.push(Key::new(&c.to_string())?)
-            }
+        Ok(key)
+    }
 
-            if c == '>' && in_tag {
-                in_tag = false;
-                keys.push(Key::new(&buf)?);
-                buf.clear();
-            }
+    /// Get the byte that the terminal sends for this `Key`.
+    ///
+    /// | key                            | byte             |
+    /// | ------------------------------ | ---------------- |
+    /// | the printable ascii, `<Space>` | the ascii byte   |
+    /// | `<c-@>` .. `<c-_>`             | `0x00` .. `0x1f` |
+    /// | `<Tab>`                        | `0x09`           |
+    /// | `<NL>`                         | `0x0a`           |
+    /// | `<CR>`                         | `0x0d`           |
+    /// | `<Esc>`                        | `0x1b`           |
+    /// | `<BS>`                         | `0x7f`           |
+    ///
+    /// Returns `None` for keys with Alt, Ctrl with Shift, and other special keys, e.g. `<F1>`.
+    ///
+    /// Some keys collide on the byte, and [Key::from_byte] returns `<Tab>` for `<c-i>`,
+    /// `<NL>` for `<c-j>`, `<CR>` for `<c-m>` and `<Esc>` for `<c-[>`.
+    /// The keys themselves are distinct, e.g. `<c-j>` is not `<NL>` and `<c-m>` is not `<CR>`.
+    ///
+    /// # Example
+    /// ```
+    /// # use viks::Key;
+    /// # fn main() {
+    /// assert_eq!(Key::new("<c-a>").unwrap().to_byte(), Some(0x01));
+    /// assert_eq!(Key::new("a").unwrap().to_byte(), Some(b'a'));
+    /// assert_eq!(Key::new("<cr>").unwrap().to_byte(), Some(0x0d));
+    /// assert_eq!(Key::new("<a-a>").unwrap().to_byte(), None);
+    /// # }
+    /// ```
+    pub fn to_byte(&self) -> Option<u8> {
+        if self.modifiers.is_alt() {
+            return None;
         }
 
-        if in_tag {
-            return Err(Error::new(s, "invalid format"));
+        if self.modifiers.is_ctrl() {
+            let c = self.code.as_ascii();
+
+            return (!self.modifiers.is_shift() && matches!(c, '@'..='_'))
+                .then_some(c as u8 & 0x1f);
         }
 
-        Ok(Keymap(keys))
+        if self.modifiers.is_shift() {
+            return self.char().filter(char::is_ascii).map(|c| c as u8);
+        }
+
+        match self.code {
+            KeyCode::Tab => Some(0x09),
+            KeyCode::LineFeed => Some(0x0a),
+            KeyCode::Enter => Some(0x0d),
+            KeyCode::Esc => Some(0x1b),
+            KeyCode::Backspace => Some(0x7f),
+            _ => self.char().filter(char::is_ascii).map(|c| c as u8),
+        }
     }
 
-    /// Get inner ref.
+    /// Parse the number of `<Char-...>`, e.g. `0x41` or `97`.
+    fn from_char_code(code: &str) -> core::result::Result<Key, ErrorKind> {
+        let code = match code.strip_prefix("0x").or_else(|| code.strip_prefix("0X")) {
+            Some(hex) => u32::from_str_radix(hex, 16),
+            None => code.parse::<u32>(),
+        };
+
+        let Ok(code) = code else {
+            return Err(ErrorKind::InvalidFormat);
+        };
+
+        char::from_u32(code)
+            .filter(char::is_ascii)
+            .and_then(|c| Key::try_from(c).ok())
+            .ok_or(ErrorKind::UnknownKey)
+    }
+
+    fn from_visible_char(c: char) -> Option<Key> {
+        let modifiers = if c.is_ascii_uppercase() {
+            KeyModifiers::SHIFT
+        } else {
+            KeyModifiers::NONE
+        };
+
+        let c = c.to_ascii_uppercase();
+
+        let code = match c {
+            'A'..='Z' => KeyCode::from_ascii(c as u8),
+            '!' | '"' | '#' | '$' | '%' | '&' | '\'' | '(' | ')' | '*' | '+' | '?' | '_' | '`'
+            | '|' | '~' | '{' | '}' | '-' | '[' | ']' | ',' | '.' | '/' | ':' | ';' | '>' | '='
+            | '@' | '\\' | '^' => KeyCode::from_ascii(c as u8),
+
+            c if c.is_ascii_digit() => KeyCode::from_ascii(c as u8),
+
+            _ => None,
+        }?;
+
+        Some(Key {
+            code,
+            modifiers,
+            payload: Payload::NONE,
+            leader: Leader::No,
+        })
+    }
+
+    /// Returns `true` if this `Key` is the alphabetic.
     ///
     /// # Example
-    ///
     /// ```
-    /// use viks::Keymap;
-    ///
+    /// # use viks::Key;
     /// # fn main() {
-    /// let keymap = Keymap::new("ZZ").unwrap();
-    ///
-    /// for key in keymap.as_vec().iter() {
-    ///     // ..
-    /// }
+    /// assert!(Key::new("a").unwrap().is_alpha());
+    /// assert!(Key::new("<c-a>").unwrap().is_alpha());
+    /// assert!(Key::new("A").unwrap().is_alpha());
+    /// assert!(!Key::new("~").unwrap().is_alpha());
+    /// assert!(!Key::new("1").unwrap().is_alpha());
+    /// assert!(!Key::new(":").unwrap().is_alpha());
     /// # }
     /// ```
-    pub fn as_vec(&self) -> &Vec<Key> {
-        &self.0
+    pub fn is_alpha(&self) -> bool {
+        self.code.as_ascii().is_ascii_uppercase()
     }
-}
 
-impl std::fmt::Display for Keymap {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(
-            f,
-            "{}",
-            self.0
-                .iter()
-                .fold(String::new(), |acc, k| format!("{acc}{k}"))
+    /// Returns `true` if this `Key` code in '0'..='9'.
+    ///
+    /// # Example
+    /// ```
+    /// # use viks::Key;
+    /// # fn main() {
+    /// assert!(!Key::new("a").unwrap().is_digit());
+    /// assert!(!Key::new("<c-a>").unwrap().is_digit());
+    /// assert!(!Key::new("A").unwrap().is_digit());
+    /// assert!(!Key::new("~").unwrap().is_digit());
+    /// assert!(Key::new("1").unwrap().is_digit());
+    /// assert!(Key::new("9").unwrap().is_digit());
+    /// assert!(!Key::new(":").unwrap().is_digit());
+    /// # }
+    /// ```
+    pub fn is_digit(&self) -> bool {
+        self.code.as_ascii().is_ascii_digit()
+    }
+
+    /// Returns `true` if this `Key` is the named key, e.g. `<CR>`, `<Space>` or `<lt>`.
+    ///
+    /// # Example
+    /// ```
+    /// # use viks::Key;
+    /// # fn main() {
+    /// assert!(Key::new("<cr>").unwrap().is_special());
+    /// assert!(Key::new("<space>").unwrap().is_special());
+    /// assert!(Key::new("<lt>").unwrap().is_special());
+    /// assert!(Key::new("<c-F1>").unwrap().is_special());
+    /// assert!(!Key::new("a").unwrap().is_special());
+    /// assert!(!Key::new("<c-a>").unwrap().is_special());
+    /// # }
+    /// ```
+    pub fn is_special(&self) -> bool {
+        self.payload == Payload::NONE && self.code.name().is_some()
+    }
+
+    /// Returns `true` if this `Key` is held with Ctrl.
+    ///
+    /// # Example
+    /// ```
+    /// # use viks::Key;
+    /// # fn main() {
+    /// assert!(Key::new("<c-a>").unwrap().is_control());
+    /// assert!(Key::new("<c-s-cr>").unwrap().is_control());
+    /// assert!(!Key::new("a").unwrap().is_control());
+    /// assert!(!Key::new("<a-a>").unwrap().is_control());
+    /// # }
+    /// ```
+    pub fn is_control(&self) -> bool {
+        self.modifiers.is_ctrl()
+    }
+
+    /// Returns `true` if this `Key` is the function key, `<F1>` to `<F24>`.
+    ///
+    /// # Example
+    /// ```
+    /// # use viks::Key;
+    /// # fn main() {
+    /// assert!(Key::new("<F1>").unwrap().is_function());
+    /// assert!(Key::new("<c-f12>").unwrap().is_function());
+    /// assert!(!Key::new("<cr>").unwrap().is_function());
+    /// assert!(!Key::new("f").unwrap().is_function());
+    /// # }
+    /// ```
+    pub fn is_function(&self) -> bool {
+        self.code.is_function()
+    }
+
+    /// Returns `true` if this `Key` is the bare modifier press without the key, e.g. only Ctrl.
+    ///
+    /// This is `false` for every `Key`, since the `Key` always has the key code.
+    /// The modifier-only tag is rejected instead of building such `Key`:
+    /// - [Key::new] returns [ErrorKind::MissingKey] for `<c->`
+    /// - [Keymap::new] returns [ErrorKind::MissingKey] for `<c->` at the end, and
+    ///   `>` after the modifier is the key before the end, e.g. `<c->>`
+    /// - the bare modifier press of the input sources fails to convert, e.g. from crossterm
+    ///
+    /// # Example
+    /// ```
+    /// # use viks::{ErrorKind, Key, Keymap};
+    /// # fn main() {
+    /// assert!(!Key::new("<c-a>").unwrap().is_modifier_only());
+    /// assert_eq!(Key::new("<c->").unwrap_err().kind(), ErrorKind::MissingKey);
+    /// assert_eq!(Keymap::new("a<c->").unwrap_err().kind(), ErrorKind::MissingKey);
+    /// # }
+    /// ```
+    pub fn is_modifier_only(&self) -> bool {
+        false
+    }
+
+    /// Returns `true` if this `Key` inserts the visible char or the space, same as [Key::char] is `Some`.
+    ///
+    /// # Example
+    /// ```
+    /// # use viks::Key;
+    /// # fn main() {
+    /// assert!(Key::new("a").unwrap().is_printable());
+    /// assert!(Key::new("A").unwrap().is_printable());
+    /// assert!(Key::new("~").unwrap().is_printable());
+    /// assert!(Key::new("<lt>").unwrap().is_printable());
+    /// assert!(Key::new("<space>").unwrap().is_printable());
+    /// assert!(!Key::new("<c-a>").unwrap().is_printable());
+    /// assert!(!Key::new("<a-a>").unwrap().is_printable());
+    /// assert!(!Key::new("<CR>").unwrap().is_printable());
+    /// assert!(!Key::new("<Esc>").unwrap().is_printable());
+    /// assert!(!Key::new("<Tab>").unwrap().is_printable());
+    /// assert!(!Key::new("<BS>").unwrap().is_printable());
+    /// assert!(!Key::new("<Del>").unwrap().is_printable());
+    /// # }
+    /// ```
+    pub fn is_printable(&self) -> bool {
+        self.char().is_some()
+    }
+
+    /// Get the ascii byte of the key code, e.g. `b'a'` for `<c-a>`.
+    ///
+    /// The modifiers are ignored, and the alphabetic is lowercase regardless of Shift
+    /// as [Code::Char], e.g. `A` and `<s-a>` are `b'a'`. Use [Key::char] for the typed char.
+    ///
+    /// Returns `None` for the non-ascii and the named keys, e.g. `<CR>`.
+    ///
+    /// # Example
+    /// ```
+    /// # use viks::Key;
+    /// # fn main() {
+    /// assert_eq!(Key::new("a").unwrap().ascii_byte(), Some(b'a'));
+    /// assert_eq!(Key::new("A").unwrap().ascii_byte(), Some(b'a'));
+    /// assert_eq!(Key::new(";").unwrap().ascii_byte(), Some(b';'));
+    /// assert_eq!(Key::new("<CR>").unwrap().ascii_byte(), None);
+    /// # }
+    /// ```
+    pub fn ascii_byte(&self) -> Option<u8> {
+        match self.code() {
+            Code::Char(c) if c.is_ascii() => Some(c as u8),
+            _ => None,
+        }
+    }
+
+    /// Get the key code of this `Key`.
+    ///
+    /// # Example
+    /// ```
+    /// # use viks::{Code, Key};
+    /// # fn main() {
+    /// let key = Key::new("<CR>").unwrap();
+    ///
+    /// match key.code() {
+    ///     Code::Enter => println!("enter"),
+    ///     Code::Char(c) if c.is_ascii_alphabetic() => println!("alphabetic {c}"),
+    ///     _ => {}
+    /// }
+    ///
+    /// assert_eq!(Key::new("<CR>").unwrap().code(), Code::Enter);
+    /// assert_eq!(Key::new("a").unwrap().code(), Code::Char('a'));
+    /// assert_eq!(Key::new("<c-f5>").unwrap().code(), Code::F(5));
+    /// # }
+    /// ```
+    pub fn code(&self) -> Code {
+        match (self.payload.char(), self.payload.tag()) {
+            (Some(c), _) => Code::Char(c),
+            (_, Some(tag)) => Code::Unknown(tag.as_str()),
+            _ => Code::from(self.code),
+        }
+    }
+
+    /// Get the printable char of this `Key`.
+    ///
+    /// Returns `None` for:
+    /// - keys with Ctrl or Alt, e.g. `<c-a>`
+    /// - shifted non-alphabetic keys, e.g. `<s-1>`
+    /// - non-printable keys, e.g. `<CR>`, `<Tab>`, `<Esc>`, `<BS>`, `<Del>`, `<F1>`
+    ///
+    /// # Example
+    /// ```
+    /// # use viks::Key;
+    /// # fn main() {
+    /// assert_eq!(Key::new("a").unwrap().char(), Some('a'));
+    /// assert_eq!(Key::new("A").unwrap().char(), Some('A'));
+    /// assert_eq!(Key::new("<s-a>").unwrap().char(), Some('A'));
+    /// assert_eq!(Key::new("<space>").unwrap().char(), Some(' '));
+    /// assert_eq!(Key::new("<lt>").unwrap().char(), Some('<'));
+    /// assert_eq!(Key::new("<c-a>").unwrap().char(), None);
+    /// assert_eq!(Key::new("<CR>").unwrap().char(), None);
+    /// # }
+    /// ```
+    pub fn char(&self) -> Option<char> {
+        if self.modifiers.is_ctrl() || self.modifiers.is_alt() {
+            return None;
+        }
+
+        let Code::Char(c) = self.code() else {
+            return None;
+        };
+
+        match (self.is_alpha(), self.modifiers.is_shift()) {
+            (true, true) => Some(c.to_ascii_uppercase()),
+            (false, true) => None,
+            (_, false) => Some(c),
+        }
+    }
+
+    /// Get the modifiers held with this `Key`.
+    ///
+    /// Shift is held for the uppercase alphabetic, e.g. `A`.
+    ///
+    /// # Example
+    /// ```
+    /// # use viks::Key;
+    /// # fn main() {
+    /// assert!(Key::new("<c-a>").unwrap().modifiers().is_ctrl());
+    /// assert!(Key::new("A").unwrap().modifiers().is_shift());
+    /// assert!(!Key::new("a").unwrap().modifiers().is_shift());
+    /// # }
+    /// ```
+    pub fn modifiers(&self) -> KeyModifiers {
+        self.modifiers
+    }
+
+    /// Get the held modifiers in the order of Ctrl, Alt, Shift.
+    ///
+    /// Shift is held for the uppercase alphabetic, e.g. `A`.
+    ///
+    /// # Example
+    /// ```
+    /// # use viks::{Key, Modifier};
+    /// # fn main() {
+    /// assert_eq!(
+    ///     Key::new("<c-s-a>").unwrap().modifiers_list(),
+    ///     vec![Modifier::Ctrl, Modifier::Shift]
+    /// );
+    /// assert_eq!(Key::new("A").unwrap().modifiers_list(), vec![Modifier::Shift]);
+    /// assert!(Key::new("a").unwrap().modifiers_list().is_empty());
+    /// # }
+    /// ```
+    pub fn modifiers_list(&self) -> Vec<Modifier> {
+        [Modifier::Ctrl, Modifier::Alt, Modifier::Shift]
+            .into_iter()
+            .filter(|m| self.modifiers.contains(KeyModifiers::from(*m)))
+            .collect()
+    }
+
+    /// Get the `Key` with the modifiers added.
+    ///
+    /// # Example
+    /// ```
+    /// # use viks::{Key, KeyModifiers};
+    /// # fn main() {
+    /// let modifiers = Key::new("<c-a-x>").unwrap().modifiers();
+    ///
+    /// assert_eq!(
+    ///     Key::new("w").unwrap().with_modifiers(modifiers),
+    ///     Key::new("<c-a-w>").unwrap()
+    /// );
+    /// assert_eq!(
+    ///     Key::new("w").unwrap().with_modifiers(KeyModifiers::CONTROL | KeyModifiers::SHIFT),
+    ///     Key::new("<c-s-w>").unwrap()
+    /// );
+    /// # }
+    /// ```
+    pub fn with_modifiers(self, modifiers: KeyModifiers) -> Key {
+        let modifiers = self.modifiers | modifiers;
+
+        // Shift-Tab is the distinct key as the terminals report, e.g. `<s-tab>` is `<BackTab>`
+        if matches!(self.code, KeyCode::Tab | KeyCode::BackTab) && modifiers.is_shift() {
+            return Key {
+                code: KeyCode::BackTab,
+                modifiers: KeyModifiers(modifiers.0 & !KeyModifiers::SHIFT.0),
+                ..self
+            };
+        }
+
+        Key { modifiers, ..self }
+    }
+
+    /// Get the `Key` with Shift added.
+    ///
+    /// # Example
+    /// ```
+    /// # use viks::Key;
+    /// # fn main() {
+    /// assert_eq!(Key::new("a").unwrap().with_shift(), Key::new("A").unwrap());
+    /// # }
+    /// ```
+    pub fn with_shift(self) -> Key {
+        self.with_modifiers(KeyModifiers::SHIFT)
+    }
+
+    /// Get the `Key` with Ctrl added.
+    ///
+    /// # Example
+    /// ```
+    /// # use viks::Key;
+    /// # fn main() {
+    /// assert_eq!(Key::new("a").unwrap().with_ctrl(), Key::new("<c-a>").unwrap());
+    /// # }
+    /// ```
+    pub fn with_ctrl(self) -> Key {
+        self.with_modifiers(KeyModifiers::CONTROL)
+    }
+
+    /// Get the `Key` with Alt added.
+    ///
+    /// # Example
+    /// ```
+    /// # use viks::Key;
+    /// # fn main() {
+    /// assert_eq!(Key::new("<cr>").unwrap().with_alt(), Key::new("<a-cr>").unwrap());
+    /// # }
+    /// ```
+    pub fn with_alt(self) -> Key {
+        self.with_modifiers(KeyModifiers::ALT)
+    }
+
+    /// Get the `Key` with Shift set, the same as [Key::with_shift].
+    ///
+    /// The shifted alphabetic is the uppercase, e.g. `a` is `A`, and `<Tab>` is `<BackTab>`.
+    ///
+    /// # Example
+    /// ```
+    /// # use viks::Key;
+    /// # fn main() {
+    /// assert_eq!(Key::new("a").unwrap().shifted(), Key::new("A").unwrap());
+    /// assert_eq!(Key::new("<c-a>").unwrap().shifted(), Key::new("<c-s-a>").unwrap());
+    /// # }
+    /// ```
+    pub fn shifted(self) -> Key {
+        self.with_shift()
+    }
+
+    /// Get the `Key` with Shift cleared, the other modifiers are kept.
+    ///
+    /// The uppercase alphabetic is the lowercase, e.g. `A` is `a`, and `<BackTab>` is `<Tab>`.
+    ///
+    /// # Example
+    /// ```
+    /// # use viks::Key;
+    /// # fn main() {
+    /// assert_eq!(Key::new("A").unwrap().unshifted(), Key::new("a").unwrap());
+    /// assert_eq!(Key::new("<c-s-a>").unwrap().unshifted(), Key::new("<c-a>").unwrap());
+    /// # }
+    /// ```
+    pub fn unshifted(self) -> Key {
+        if self.code == KeyCode::BackTab {
+            return Key {
+                code: KeyCode::Tab,
+                ..self
+            };
+        }
+
+        Key {
+            modifiers: KeyModifiers(self.modifiers.0 & !KeyModifiers::SHIFT.0),
+            ..self
+        }
+    }
+
+    /// Returns `true` if this `Key` can be repeated by a count.
+    ///
+    /// Mouse keys and pseudo keys (`<Nop>`, `<Plug>`) are not repeatable.
+    ///
+    /// # Example
+    /// ```
+    /// # use viks::Key;
+    /// # fn main() {
+    /// assert!(Key::new("a").unwrap().is_repeatable());
+    /// assert!(Key::new("<c-cr>").unwrap().is_repeatable());
+    /// assert!(!Key::new("<LeftMouse>").unwrap().is_repeatable());
+    /// # }
+    /// ```
+    pub fn is_repeatable(&self) -> bool {
+        !self.code.is_mouse() && !self.code.is_pseudo()
+    }
+
+    /// Get the `Display` output without the surrounding `<>`, e.g. `CR` or `c-a`.
+    ///
+    /// # Example
+    /// ```
+    /// # use viks::Key;
+    /// # fn main() {
+    /// assert_eq!(Key::new("<Enter>").unwrap().name(), "CR");
+    /// assert_eq!(Key::new("<space>").unwrap().name(), "SPACE");
+    /// assert_eq!(Key::new("<c-a>").unwrap().name(), "c-a");
+    /// assert_eq!(Key::new("a").unwrap().name(), "a");
+    /// assert_eq!(Key::new("A").unwrap().name(), "A");
+    /// # }
+    /// ```
+    pub fn name(&self) -> String {
+        let display = self.to_string();
+
+        match display.strip_prefix('<') {
+            Some(inner) => inner[..inner.len() - 1].to_string(),
+            None => display,
+        }
+    }
+
+    /// Get a human readable description, e.g. `Ctrl+Shift+A`.
+    ///
+    /// Modifiers are listed in the order `Ctrl`, `Alt`, `Shift`.
+    ///
+    /// # Example
+    /// ```
+    /// # use viks::Key;
+    /// # fn main() {
+    /// assert_eq!(Key::new("a").unwrap().describe(), "A");
+    /// assert_eq!(Key::new("A").unwrap().describe(), "Shift+A");
+    /// assert_eq!(Key::new("<c-cr>").unwrap().describe(), "Ctrl+Enter");
+    /// assert_eq!(Key::new("<leader>").unwrap().describe(), "Leader");
+    /// assert_eq!(Key::new("<space>").unwrap().describe(), "Space");
+    /// # }
+    /// ```
+    pub fn describe(&self) -> String {
+        let code = match self.code {
+            _ if self.leader != Leader::No => "Leader".to_string(),
+            KeyCode::Enter => "Enter".to_string(),
+            KeyCode::Tab => "Tab".to_string(),
+            KeyCode::Esc => "Esc".to_string(),
+            KeyCode::Space => "Space".to_string(),
+            KeyCode::Backspace => "Backspace".to_string(),
+            KeyCode::Delete => "Delete".to_string(),
+            KeyCode::LessThanSign => "<".to_string(),
+            keycode => match (self.payload.char(), self.payload.tag(), keycode.name()) {
+                (Some(c), _, _) => c.to_string(),
+                (_, Some(tag), _) => tag.as_str().to_string(),
+                (_, _, Some(name)) => name.to_string(),
+                _ => keycode.as_ascii().to_string(),
+            },
+        };
+
+        let mut desc = String::new();
+
+        if self.modifiers.is_ctrl() {
+            desc.push_str("Ctrl+");
+        }
+
+        if self.modifiers.is_alt() {
+            desc.push_str("Alt+");
+        }
+
+        if self.modifiers.is_shift() {
+            desc.push_str("Shift+");
+        }
+
+        desc.push_str(&code);
+
+        desc
+    }
+}
+
+/// The output re-parses to the same key with [Key::new] and [Keymap::new], except the keys
+/// that need the [ParseOptions] to parse:
+/// - the unicode key, e.g. `é`, re-parses with [ParseOptions::unicode]
+/// - the unknown key is written as its tag, e.g. `<c-BOO>`, and re-parses with [ParseOptions::strict] disabled
+/// - the key written as `<leader>` re-parses with the same [ParseOptions::leader]
+///
+/// The alternate flag `{:#}` writes the key names in lowercase, e.g. `<cr>` instead of `<CR>`,
+/// the unknown tag is always written verbatim.
+///
+/// The key written as `<leader>` is `<leader>` with [ParseOptions::preserve_leader],
+/// otherwise it is the resolved key, e.g. `<SPACE>`.
+impl core::fmt::Display for Key {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        use core::fmt::Write;
+
+        let is_shift = self.modifiers.is_shift();
+        let is_alpha = matches!(self.code as u8, 65..=90);
+        let is_case_shift = is_alpha && !self.modifiers.is_ctrl() && !self.modifiers.is_alt();
+        let has_prefix =
+            self.modifiers.is_ctrl() || self.modifiers.is_alt() || (is_shift && !is_case_shift);
+        let tag = self.payload.tag().map(Tag::as_str);
+        let name = tag.or(self.code.name());
+        let is_leader = self.leader == Leader::Preserved;
+        let is_tag = name.is_some() || has_prefix || is_leader;
+
+        if is_tag {
+            f.write_char('<')?;
+        }
+
+        if self.modifiers.is_ctrl() {
+            f.write_str("c-")?;
+        }
+
+        if self.modifiers.is_alt() {
+            f.write_str("a-")?;
+        }
+
+        if is_shift && !is_case_shift {
+            f.write_str("s-")?;
+        }
+
+        match (self.payload.char(), name) {
+            _ if is_leader => f.write_str("leader")?,
+            (Some(c), _) => f.write_char(c)?,
+            (None, Some(name)) if f.alternate() && tag.is_none() => {
+                for c in name.chars() {
+                    f.write_char(c.to_ascii_lowercase())?;
+                }
+            }
+            (None, Some(name)) => f.write_str(name)?,
+            (None, None) if is_alpha && !(is_shift && is_case_shift) => {
+                f.write_char(self.code.as_ascii().to_ascii_lowercase())?
+            }
+            (None, None) => f.write_char(self.code.as_ascii())?,
+        }
+
+        if is_tag {
+            f.write_char('>')?;
+        }
+
+        Ok(())
+    }
+}
+
+impl PartialEq for Key {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other).is_eq()
+    }
+}
+
+impl Eq for Key {}
+
+impl PartialOrd for Key {
+    fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// The `<leader>` is the same as the resolved key.
+impl Ord for Key {
+    fn cmp(&self, other: &Self) -> core::cmp::Ordering {
+        (self.code, self.modifiers, self.payload).cmp(&(other.code, other.modifiers, other.payload))
+    }
+}
+
+impl core::hash::Hash for Key {
+    fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+        (self.code, self.modifiers, self.payload).hash(state);
+    }
+}
+
+impl core::fmt::Debug for Key {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self.code() {
+            Code::Char(c) if self.payload.char().is_some() => write!(
+                f,
+                "Key {{ code: {c:?}, modifiers: {:#05b} }}",
+                self.modifiers.0
+            ),
+            Code::Unknown(tag) => write!(
+                f,
+                "Key {{ code: Unknown({tag:?}), modifiers: {:#05b} }}",
+                self.modifiers.0
+            ),
+            _ => write!(
+                f,
+                "Key {{ code: {}, modifiers: {:#05b} }}",
+                self.code as u8, self.modifiers.0
+            ),
+        }
+    }
+}
+
+/// Parse with [Key::new].
+///
+/// # Example
+/// ```
+/// use viks::Key;
+///
+/// # fn main() {
+/// let key = "<c-a>".parse::<Key>().unwrap();
+///
+/// assert_eq!(key, Key::new("<c-a>").unwrap());
+/// # }
+/// ```
+impl core::str::FromStr for Key {
+    type Err = Error;
+
+    fn from_str(s: &str) -> core::result::Result<Self, Self::Err> {
+        Key::new(s)
+    }
+}
+
+/// Compare with the key parsed by [Key::new], the unavailable key is not equal.
+///
+/// # Example
+/// ```
+/// use viks::Key;
+///
+/// # fn main() {
+/// let key = Key::new("<s-a>").unwrap();
+///
+/// assert_eq!(key, "A");
+/// assert_eq!(key, *"<S-A>");
+/// assert_ne!(key, "a");
+/// assert_ne!(key, "<unknown>");
+/// # }
+/// ```
+impl PartialEq<str> for Key {
+    fn eq(&self, other: &str) -> bool {
+        Key::new(other).is_ok_and(|key| *self == key)
+    }
+}
+
+/// Same as `PartialEq<str>`.
+impl PartialEq<&str> for Key {
+    fn eq(&self, other: &&str) -> bool {
+        *self == **other
+    }
+}
+
+/// Convert the char to the `Key` without allocation.
+///
+/// The printable ascii and the control chars of
+/// `Tab`, `Enter`, `Esc`, `Space`, `Backspace` and `Delete` are available.
+///
+/// # Example
+///
+/// ```
+/// use viks::Key;
+///
+/// # fn main() {
+/// assert_eq!(Key::try_from('A').unwrap(), Key::new("<s-a>").unwrap());
+/// assert_eq!(Key::try_from('\t').unwrap(), Key::new("<tab>").unwrap());
+/// assert!(Key::try_from('あ').is_err());
+/// # }
+/// ```
+impl TryFrom<char> for Key {
+    type Error = Error;
+
+    fn try_from(value: char) -> core::result::Result<Self, Self::Error> {
+        let mut buf = [0; 4];
+        let tag = value.encode_utf8(&mut buf);
+
+        if !value.is_ascii() {
+            return Err(Error::new(ErrorKind::NotAscii, tag));
+        }
+
+        let code = match value {
+            '\t' | '\r' | '\x1b' | ' ' | '\x08' | '\x7f' | '<' => KeyCode::from_ascii(value as u8),
+            _ => {
+                return Key::from_visible_char(value)
+                    .ok_or_else(|| Error::new(ErrorKind::UnknownKey, tag));
+            }
+        };
+
+        code.map(|code| Key {
+            code,
+            modifiers: KeyModifiers::NONE,
+            payload: Payload::NONE,
+            leader: Leader::No,
+        })
+        .ok_or_else(|| Error::new(ErrorKind::UnknownKey, tag))
+    }
+}
+
+/// Same as [Key::new].
+impl TryFrom<&str> for Key {
+    type Error = Error;
+
+    fn try_from(value: &str) -> core::result::Result<Self, Self::Error> {
+        Key::new(value)
+    }
+}
+
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+/// Wrapper of [Vec]<[Key]>.
+///
+/// This only has parse func, please use `as_vec` to access to inner.
+///
+/// `Eq`, `Hash` and `Ord` compare the keys in order, as normalized by [Key].
+/// `Ord` is lexicographic, so the empty keymap and the prefix sort first, e.g. `g` < `gg` < `h`.
+///
+/// # Example
+///
+/// ```
+/// use viks::Keymap;
+///
+/// # fn main() {
+/// let exit_map = Keymap::new("ZZ").unwrap();
+/// let exit_map_alt = Keymap::new("<s-z>Z").unwrap();
+///
+/// assert_eq!(exit_map, exit_map_alt);
+/// # }
+/// ```
+pub struct Keymap(Vec<Key>);
+
+impl Keymap {
+    /// Create new Keymap.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use viks::Keymap;
+    ///
+    /// # fn main() {
+    /// let exit_map = Keymap::new("ZZ").unwrap();
+    /// let exit_map_alt = Keymap::new("<s-z>Z").unwrap();
+    /// let exit_map_owned = Keymap::new(String::from("ZZ")).unwrap();
+    ///
+    /// assert_eq!(exit_map, exit_map_alt);
+    /// assert_eq!(exit_map, exit_map_owned);
+    /// # }
+    /// ```
+    ///
+    /// # Error
+    ///
+    /// Returns an error if the tag is not closed or nested, or any key is not available.
+    /// The error has the byte offset of the failed key, the unclosed `<` or the nested `<`,
+    /// and the index of the failed key, e.g. `2` for `<BOO>` of `ab<BOO>cd`.
+    ///
+    /// The `>` out of the tag is the key itself, e.g. `a>b` is `a`, `>` and `b`.
+    /// The `>` after the modifier is the key, e.g. `<c->>`, and `<c->` at the end is [ErrorKind::MissingKey].
+    pub fn new(s: impl AsRef<str>) -> self::Result<Self> {
+        Keymap::new_with_options(s.as_ref(), &ParseOptions::default())
+    }
+
+    /// Create new Keymap with [ParseOptions].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use viks::{Keymap, ParseOptions};
+    ///
+    /// # fn main() {
+    /// let options = ParseOptions {
+    ///     literal_unknown_tags: true,
+    ///     ..Default::default()
+    /// };
+    ///
+    /// assert!(Keymap::new("<abc>").is_err());
+    /// assert_eq!(
+    ///     Keymap::new_with_options("<abc>", &options).unwrap(),
+    ///     Keymap::new("abc").unwrap()
+    /// );
+    /// # }
+    /// ```
+    ///
+    /// # Error
+    ///
+    /// Returns an error if the tag is not closed.
+    pub fn new_with_options(s: &str, options: &ParseOptions) -> self::Result<Self> {
+        let mut keys = vec![];
+
+        Keymap::parse_each(s, options, |key, _| keys.push(key))?;
+
+        Ok(Keymap(keys))
+    }
+
+    /// Check that the string is available as Keymap, without collecting the keys.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use viks::Keymap;
+    ///
+    /// # fn main() {
+    /// assert!(Keymap::validate("<leader>w<CR>").is_ok());
+    /// assert_eq!(Keymap::validate("a<BOO>"), Keymap::new("a<BOO>").map(|_| ()));
+    /// # }
+    /// ```
+    ///
+    /// # Error
+    ///
+    /// Returns the same error as [Keymap::new].
+    pub fn validate(s: &str) -> self::Result<()> {
+        Keymap::parse_each(s, &ParseOptions::default(), |_, _| {})
+    }
+
+    /// Parse the string and pass each key and its byte range in the string to `push`.
+    pub(crate) fn parse_each<F: FnMut(Key, Range<usize>)>(
+        s: &str,
+        options: &ParseOptions,
+        mut push: F,
+    ) -> self::Result<()> {
+        let index = core::cell::Cell::new(0);
+
+        Keymap::parse_tokens(s, options, |key, range| {
+            push(key, range);
+            index.set(index.get() + 1);
+        })
+        .map_err(|e| e.with_index(index.get()))
+    }
+
+    fn parse_tokens<F: FnMut(Key, Range<usize>)>(
+        s: &str,
+        options: &ParseOptions,
+        mut push: F,
+    ) -> self::Result<()> {
+        let mut in_tag = false;
+        let mut tag_start = 0;
+        let mut escape_start = None;
+
+        for (i, c) in s.char_indices() {
+            if let Some(start) = escape_start.take() {
+                if !matches!(c, '<' | '\\') {
+                    return Err(Error::new(ErrorKind::InvalidFormat, s).with_offset(start));
+                }
+
+                push(Key::try_from(c)?, start..i + 1);
+
+                continue;
+            }
+
+            if c == '\\' && !in_tag && options.backslash_escape {
+                escape_start = Some(i);
+
+                continue;
+            }
+
+            // `>` after the modifier is the key itself, e.g. `<c->>`
+            let is_modded_gt = in_tag && is_modifier_prefix(&s[tag_start..i]);
+
+            if c == '<' && in_tag && !is_modded_gt {
+                return Err(Error::new(ErrorKind::NestedTag, s).with_offset(i));
+            }
+
+            if c == '<' && !in_tag {
+                in_tag = true;
+                tag_start = i;
+            }
+
+            if !in_tag {
+                let key = Key::from_char(c, options).map_err(|e| e.with_offset(i))?;
+
+                push(key, i..i + c.len_utf8());
+            }
+
+            if c == '>' && in_tag && !is_modded_gt {
+                let tag = &s[tag_start..=i];
+
+                in_tag = false;
+
+                match Key::new_with_options(tag, options) {
+                    Ok(key) => push(key, tag_start..i + 1),
+                    Err(e) if options.literal_unknown_tags => {
+                        let inner = &tag[1..tag.len() - 1];
+
+                        if inner.chars().any(|c| Key::from_char(c, options).is_err()) {
+                            return Err(e.with_offset(tag_start));
+                        }
+
+                        for (j, c) in inner.char_indices() {
+                            let start = tag_start + 1 + j;
+
+                            push(Key::from_char(c, options)?, start..start + c.len_utf8());
+                        }
+                    }
+                    Err(e) => return Err(e.with_offset(tag_start)),
+                }
+            }
+        }
+
+        if in_tag && is_modifier_only_tag(&s[tag_start..]) {
+            return Err(Error::new(ErrorKind::MissingKey, s).with_offset(tag_start));
+        }
+
+        if in_tag {
+            return Err(Error::new(ErrorKind::UnclosedTag, s).with_offset(tag_start));
+        }
+
+        if let Some(start) = escape_start {
+            return Err(Error::new(ErrorKind::InvalidFormat, s).with_offset(start));
+        }
+
+        Ok(())
+    }
+
+    /// Parse the whitespace-separated keymaps, e.g. `gg G dd`.
+    ///
+    /// The space in each keymap is `<Space>` or `<leader>`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use viks::Keymap;
+    ///
+    /// # fn main() {
+    /// let keymaps = Keymap::parse_sequence("gg <leader>w  dd").unwrap();
+    ///
+    /// assert_eq!(keymaps.len(), 3);
+    /// assert_eq!(keymaps[1], Keymap::new("<space>w").unwrap());
+    /// # }
+    /// ```
+    ///
+    /// # Error
+    ///
+    /// Returns an error if any keymap is not available.
+    /// The error has the byte offset in the whole string.
+    pub fn parse_sequence(s: &str) -> self::Result<Vec<Keymap>> {
+        s.split_ascii_whitespace()
+            .map(|token| {
+                let start = token.as_ptr() as usize - s.as_ptr() as usize;
+
+                Keymap::new(token).map_err(|e| match e.offset() {
+                    Some(offset) => e.with_offset(offset + start),
+                    None => e,
+                })
+            })
+            .collect()
+    }
+
+    /// Remove the duplicated keymaps, keeping the first-seen order.
+    ///
+    /// The keymaps are compared as `Eq`, e.g. `<Enter>` and `<CR>` are duplicated.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use viks::Keymap;
+    ///
+    /// # fn main() {
+    /// let keymaps = Keymap::parse_sequence("dd <leader>w dd <space>w").unwrap();
+    ///
+    /// assert_eq!(
+    ///     Keymap::dedup(keymaps),
+    ///     Keymap::parse_sequence("dd <leader>w").unwrap()
+    /// );
+    /// # }
+    /// ```
+    pub fn dedup(mut keymaps: Vec<Keymap>) -> Vec<Keymap> {
+        let mut seen = BTreeSet::new();
+
+        keymaps.retain(|keymap| seen.insert(keymap.clone()));
+        keymaps
+    }
+
+    /// Remove the consecutive duplicated keymaps, same as [Vec::dedup].
+    ///
+    /// The keymaps are compared as `Eq`, and the separated duplicates are kept,
+    /// use [Keymap::dedup] to remove all duplicates.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use viks::Keymap;
+    ///
+    /// # fn main() {
+    /// let keymaps = Keymap::parse_sequence("dd dd <leader>w <space>w dd").unwrap();
+    ///
+    /// assert_eq!(
+    ///     Keymap::dedup_consecutive(keymaps),
+    ///     Keymap::parse_sequence("dd <leader>w dd").unwrap()
+    /// );
+    /// # }
+    /// ```
+    pub fn dedup_consecutive(mut keymaps: Vec<Keymap>) -> Vec<Keymap> {
+        keymaps.dedup();
+        keymaps
+    }
+
+    /// Get inner ref.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use viks::Keymap;
+    ///
+    /// # fn main() {
+    /// let keymap = Keymap::new("ZZ").unwrap();
+    ///
+    /// for key in keymap.as_vec().iter() {
+    ///     // ..
+    /// }
+    /// # }
+    /// ```
+    pub fn as_vec(&self) -> &Vec<Key> {
+        &self.0
+    }
+
+    /// Get the keys as the slice.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use viks::{Key, Keymap};
+    ///
+    /// # fn main() {
+    /// let keymap = Keymap::new("<c-w>j").unwrap();
+    ///
+    /// assert_eq!(keymap.as_slice(), &[Key::new("<c-w>").unwrap(), Key::new("j").unwrap()]);
+    /// # }
+    /// ```
+    pub fn as_slice(&self) -> &[Key] {
+        &self.0
+    }
+
+    /// Get the iterator over the keys.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use viks::Keymap;
+    ///
+    /// # fn main() {
+    /// let keymap = Keymap::new("<c-w>jk<CR>").unwrap();
+    ///
+    /// assert_eq!(keymap.iter().filter(|key| key.is_alpha()).count(), 3);
+    /// # }
+    /// ```
+    pub fn iter(&self) -> core::slice::Iter<'_, Key> {
+        self.0.iter()
+    }
+
+    /// Get the number of keys.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use viks::Keymap;
+    ///
+    /// # fn main() {
+    /// assert_eq!(Keymap::new("ZZ").unwrap().len(), 2);
+    /// assert_eq!(Keymap::new("<leader>w").unwrap().len(), 2);
+    /// # }
+    /// ```
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Returns `true` if this Keymap has no keys.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use viks::Keymap;
+    ///
+    /// # fn main() {
+    /// assert!(Keymap::new("").unwrap().is_empty());
+    /// assert!(!Keymap::new("ZZ").unwrap().is_empty());
+    /// # }
+    /// ```
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Get the key at the index.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use viks::{Key, Keymap};
+    ///
+    /// # fn main() {
+    /// let keymap = Keymap::new("<c-w>q").unwrap();
+    ///
+    /// assert_eq!(keymap.get(0), Some(&Key::new("<c-w>").unwrap()));
+    /// assert_eq!(keymap.get(2), None);
+    /// # }
+    /// ```
+    pub fn get(&self, index: usize) -> Option<&Key> {
+        self.0.get(index)
+    }
+
+    /// Append the key to the end.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use viks::{Key, Keymap};
+    ///
+    /// # fn main() {
+    /// let mut keymap = Keymap::new("").unwrap();
+    ///
+    /// keymap.push(Key::new("<c-w>").unwrap());
+    /// keymap.push(Key::new("q").unwrap());
+    ///
+    /// assert_eq!(keymap, Keymap::new("<c-w>q").unwrap());
+    /// # }
+    /// ```
+    pub fn push(&mut self, key: Key) {
+        self.0.push(key);
+    }
+
+    /// Returns `true` if the `prefix` matches the leading keys of this `Keymap`.
+    ///
+    /// An empty `prefix` always matches.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use viks::Keymap;
+    ///
+    /// # fn main() {
+    /// let keymap = Keymap::new("gg").unwrap();
+    ///
+    /// assert!(keymap.starts_with(&Keymap::new("g").unwrap()));
+    /// assert!(!keymap.starts_with(&Keymap::new("gd").unwrap()));
+    /// # }
+    /// ```
+    pub fn starts_with(&self, prefix: &Keymap) -> bool {
+        self.0.starts_with(&prefix.0)
+    }
+
+    /// Get the keys after the `prefix`, or `None` if the `prefix` doesn't match.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use viks::Keymap;
+    ///
+    /// # fn main() {
+    /// let keymap = Keymap::new("gg").unwrap();
+    ///
+    /// assert_eq!(keymap.strip_prefix(&Keymap::new("g").unwrap()), Some(Keymap::new("g").unwrap()));
+    /// assert_eq!(keymap.strip_prefix(&Keymap::new("d").unwrap()), None);
+    /// # }
+    /// ```
+    pub fn strip_prefix(&self, prefix: &Keymap) -> Option<Keymap> {
+        self.0
+            .strip_prefix(prefix.0.as_slice())
+            .map(|rest| Keymap(rest.to_vec()))
+    }
+
+    /// Returns `true` if the `key` is in this `Keymap`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use viks::{Key, Keymap};
+    ///
+    /// # fn main() {
+    /// let keymap = Keymap::new("i<Esc>").unwrap();
+    ///
+    /// assert!(keymap.contains(&Key::ESC));
+    /// assert!(!keymap.contains(&Key::ENTER));
+    /// # }
+    /// ```
+    pub fn contains(&self, key: &Key) -> bool {
+        self.0.contains(key)
+    }
+
+    /// Replace every `from` key with the `to` key, and get the number of the replaced keys.
+    ///
+    /// The keys are compared as `Eq`, e.g. `<leader>` is the same as `<Space>`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use viks::{Key, Keymap};
+    ///
+    /// # fn main() {
+    /// let mut keymap = Keymap::new("<leader>w<leader>q").unwrap();
+    ///
+    /// assert_eq!(keymap.replace_key(&Key::SPACE, Key::new(",").unwrap()), 2);
+    /// assert_eq!(keymap, Keymap::new(",w,q").unwrap());
+    /// # }
+    /// ```
+    pub fn replace_key(&mut self, from: &Key, to: Key) -> usize {
+        let mut count = 0;
+
+        for key in self.0.iter_mut() {
+            if key == from {
+                *key = to;
+                count += 1;
+            }
+        }
+
+        count
+    }
+
+    /// Get the keys holding the modifier in order.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use viks::{Key, Keymap, Modifier};
+    ///
+    /// # fn main() {
+    /// let keymap = Keymap::new("<c-a>B<a-c>").unwrap();
+    ///
+    /// assert_eq!(
+    ///     keymap.keys_with_modifier(Modifier::Shift),
+    ///     vec![&Key::new("B").unwrap()]
+    /// );
+    /// # }
+    /// ```
+    pub fn keys_with_modifier(&self, modifier: Modifier) -> Vec<&Key> {
+        let modifier = KeyModifiers::from(modifier);
+
+        self.0
+            .iter()
+            .filter(|key| key.modifiers.contains(modifier))
+            .collect()
+    }
+
+    /// Classify the typed keys against this `Keymap`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use viks::{Key, Keymap, MatchResult};
+    ///
+    /// # fn main() {
+    /// let keymap = Keymap::new("dd").unwrap();
+    /// let d = Key::new("d").unwrap();
+    ///
+    /// assert_eq!(keymap.matches(&[d]), MatchResult::Partial);
+    /// assert_eq!(keymap.matches(&[d, d]), MatchResult::Full);
+    /// # }
+    /// ```
+    pub fn matches(&self, input: &[Key]) -> MatchResult {
+        if self.0 == input {
+            MatchResult::Full
+        } else if self.0.starts_with(input) {
+            MatchResult::Partial
+        } else {
+            MatchResult::None
+        }
+    }
+
+    /// Split into the first key and the remaining keys.
+    ///
+    /// Returns `None` if this `Keymap` is empty.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use viks::{Key, Keymap};
+    ///
+    /// # fn main() {
+    /// let keymap = Keymap::new("<c-b>jj").unwrap();
+    /// let (first, rest) = keymap.split_first().unwrap();
+    ///
+    /// assert_eq!(first, &Key::new("<c-b>").unwrap());
+    /// assert_eq!(rest, Keymap::new("jj").unwrap());
+    /// # }
+    /// ```
+    pub fn split_first(&self) -> Option<(&Key, Keymap)> {
+        self.0
+            .split_first()
+            .map(|(first, rest)| (first, Keymap(rest.to_vec())))
+    }
+
+    /// Split into the leader and the remaining keys, e.g. `<leader>w` into `<leader>` and `w`.
+    ///
+    /// Returns `None` if this `Keymap` doesn't start with the `leader`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use viks::{Key, Keymap, ParseOptions};
+    ///
+    /// # fn main() {
+    /// let options = ParseOptions {
+    ///     leader: Key::new(",").unwrap(),
+    ///     ..Default::default()
+    /// };
+    /// let keymap = Keymap::new_with_options("<leader>w", &options).unwrap();
+    /// let (leader, rest) = keymap.split_at_leader(&options.leader).unwrap();
+    ///
+    /// assert_eq!(leader, Keymap::new(",").unwrap());
+    /// assert_eq!(rest, Keymap::new("w").unwrap());
+    /// # }
+    /// ```
+    pub fn split_at_leader(&self, leader: &Key) -> Option<(Keymap, Keymap)> {
+        let (first, rest) = self.split_first()?;
+
+        (first == leader).then(|| (Keymap(vec![*first]), rest))
+    }
+
+    /// Create new Keymap that only contains the keys matching the predicate.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use viks::Keymap;
+    ///
+    /// # fn main() {
+    /// let keymap = Keymap::new("<leader>w<cr>").unwrap();
+    ///
+    /// assert_eq!(keymap.retain_keys(|k| k.is_alpha()), Keymap::new("w").unwrap());
+    /// # }
+    /// ```
+    pub fn retain_keys<F: FnMut(&Key) -> bool>(&self, mut f: F) -> Keymap {
+        Keymap(self.0.iter().filter(|k| f(k)).copied().collect())
+    }
+
+    /// Get the byte length of the `Display` output.
+    ///
+    /// Useful as a capacity hint when rendering many keymaps into one buffer.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use viks::Keymap;
+    ///
+    /// # fn main() {
+    /// let keymap = Keymap::new("<leader>w<cr>").unwrap();
+    ///
+    /// assert_eq!(keymap.display_byte_len(), "<SPACE>w<CR>".len());
+    /// # }
+    /// ```
+    pub fn display_byte_len(&self) -> usize {
+        struct Counter(usize);
+
+        impl core::fmt::Write for Counter {
+            fn write_str(&mut self, s: &str) -> core::fmt::Result {
+                self.0 += s.len();
+
+                Ok(())
+            }
+        }
+
+        let mut counter = Counter(0);
+
+        for key in self.0.iter() {
+            use core::fmt::Write;
+
+            let _ = write!(counter, "{key}");
+        }
+
+        counter.0
+    }
+
+    /// Get the canonical string, the same for every equal `Keymap`.
+    ///
+    /// This is the `Display` output without [ParseOptions::preserve_leader], and the rules are:
+    /// - modifier prefixes are lowercase and ordered `c-`, `a-`, `s-`
+    /// - the shifted alphabetic without other modifiers is uppercase, e.g. `A`
+    /// - other alphabetics are lowercase, e.g. `<c-s-a>`
+    /// - special keys use one name each, e.g. `<CR>` for `<Enter>`
+    ///   and `<SPACE>` for `<Space>` and `<leader>`
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use viks::Keymap;
+    ///
+    /// # fn main() {
+    /// let keymap1 = Keymap::new("<Enter><S-C-A>").unwrap();
+    /// let keymap2 = Keymap::new("<cr><ctrl-shift-a>").unwrap();
+    ///
+    /// assert_eq!(keymap1.canonical(), "<CR><c-s-a>");
+    /// assert_eq!(keymap1.canonical(), keymap2.canonical());
+    /// # }
+    /// ```
+    pub fn canonical(&self) -> String {
+        self.0
+            .iter()
+            .map(|key| Key {
+                leader: Leader::No,
+                ..*key
+            })
+            .collect::<Keymap>()
+            .to_string()
+    }
+
+    /// Convert to the flat [KeyToken] representation.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use viks::Keymap;
+    ///
+    /// # fn main() {
+    /// let keymap = Keymap::new("<c-w>q").unwrap();
+    /// let tokens = keymap.to_tokens();
+    ///
+    /// assert_eq!(tokens[0].name, "w");
+    /// assert!(tokens[0].ctrl);
+    /// assert_eq!(Keymap::from_tokens(&tokens).unwrap(), keymap);
+    /// # }
+    /// ```
+    pub fn to_tokens(&self) -> Vec<KeyToken> {
+        self.0.iter().map(|k| KeyToken::from(*k)).collect()
+    }
+
+    /// Create new Keymap from the flat [KeyToken] representation.
+    ///
+    /// # Error
+    ///
+    /// Returns an error if any token is not available.
+    pub fn from_tokens(tokens: &[KeyToken]) -> self::Result<Self> {
+        tokens
+            .iter()
+            .map(Key::try_from)
+            .collect::<self::Result<Vec<_>>>()
+            .map(Keymap)
+    }
+
+    /// Create new Keymap from the bytes of the raw terminal input.
+    ///
+    /// Each byte is one key, see [Key::from_byte].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use viks::Keymap;
+    ///
+    /// # fn main() {
+    /// let keymap = Keymap::from_bytes(b"\x17q\r").unwrap();
+    ///
+    /// assert_eq!(keymap, Keymap::new("<c-w>q<cr>").unwrap());
+    /// # }
+    /// ```
+    ///
+    /// # Error
+    ///
+    /// Returns an error if any byte is not ascii.
+    /// The error has the offset and the index of the failed byte, they are the same as one byte is one key.
+    pub fn from_bytes(bytes: &[u8]) -> self::Result<Self> {
+        bytes
+            .iter()
+            .enumerate()
+            .map(|(i, b)| Key::from_byte(*b).map_err(|e| e.with_offset(i).with_index(i)))
+            .collect::<self::Result<Vec<_>>>()
+            .map(Keymap)
+    }
+
+    /// Create new key from the escape sequence of the raw terminal input, e.g. `b"\x1b[A"`.
+    ///
+    /// | sequence                     | key                                   |
+    /// | ---------------------------- | ------------------------------------- |
+    /// | `ESC [ A` .. `ESC [ D`       | `<Up>`, `<Down>`, `<Right>`, `<Left>` |
+    /// | `ESC [ H`, `ESC [ F`         | `<Home>`, `<End>`                     |
+    /// | `ESC [ Z`                    | `<BackTab>`                           |
+    /// | `ESC [ P` .. `ESC [ S`       | `<F1>` .. `<F4>`                      |
+    /// | `ESC [ 1 ~`, `ESC [ 4 ~`     | `<Home>`, `<End>`, also `7` and `8`   |
+    /// | `ESC [ 2 ~`, `ESC [ 3 ~`     | `<Insert>`, `<Del>`                   |
+    /// | `ESC [ 5 ~`, `ESC [ 6 ~`     | `<PageUp>`, `<PageDown>`              |
+    /// | `ESC [ 11 ~` .. `ESC [ 24 ~` | `<F1>` .. `<F12>`                     |
+    /// | `ESC O A` .. `ESC O S`       | same as `ESC [`                       |
+    /// | `ESC` and the byte           | the key of the byte with Alt          |
+    /// | the byte                     | same as [Key::from_byte]              |
+    ///
+    /// The modifiers are the second parameter, e.g. `ESC [ 1 ; 5 A` is `<c-up>`,
+    /// as `1` plus the bits of Shift `1`, Alt `2` and Ctrl `4`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use viks::{Key, Keymap};
+    ///
+    /// # fn main() {
+    /// assert_eq!(Keymap::from_escape_sequence(b"\x1b[A").unwrap(), Key::new("<Up>").unwrap());
+    /// assert_eq!(Keymap::from_escape_sequence(b"\x1b[15;2~").unwrap(), Key::new("<s-F5>").unwrap());
+    /// assert_eq!(Keymap::from_escape_sequence(b"\x1bj").unwrap(), Key::new("<a-j>").unwrap());
+    /// # }
+    /// ```
+    ///
+    /// # Error
+    ///
+    /// Returns an error if the sequence is empty or not the supported key.
+    pub fn from_escape_sequence(seq: &[u8]) -> self::Result<Key> {
+        let unsupported = || Error::new(ErrorKind::UnknownKey, &seq.escape_ascii().to_string());
+
+        let (params, final_byte) = match seq {
+            [] => return Err(Error::new(ErrorKind::Empty, "")),
+            [b] => return Key::from_byte(*b),
+            [0x1b, b'O', last] if last.is_ascii_uppercase() => (&[][..], *last),
+            [0x1b, b'[', params @ .., last] => (params, *last),
+            [0x1b, b] => return Key::from_byte(*b).map(Key::with_alt),
+            _ => return Err(unsupported()),
+        };
+
+        let params = params
+            .split(|b| *b == b';')
+            .map(|param| match param {
+                [] => Some(1),
+                param => core::str::from_utf8(param).ok()?.parse::<u8>().ok(),
+            })
+            .collect::<Option<Vec<_>>>()
+            .ok_or_else(unsupported)?;
+
+        let (number, modifier) = match params[..] {
+            [number] => (number, 1),
+            [number, modifier] => (number, modifier),
+            _ => return Err(unsupported()),
+        };
+
+        let code = match (final_byte, number) {
+            (b'A', 1) => Some(KeyCode::Up),
+            (b'B', 1) => Some(KeyCode::Down),
+            (b'C', 1) => Some(KeyCode::Right),
+            (b'D', 1) => Some(KeyCode::Left),
+            (b'H', 1) | (b'~', 1 | 7) => Some(KeyCode::Home),
+            (b'F', 1) | (b'~', 4 | 8) => Some(KeyCode::End),
+            (b'Z', 1) => Some(KeyCode::BackTab),
+            (b'P'..=b'S', 1) => KeyCode::from_function(final_byte - b'P' + 1),
+            (b'~', 2) => Some(KeyCode::Insert),
+            (b'~', 3) => Some(KeyCode::Delete),
+            (b'~', 5) => Some(KeyCode::PageUp),
+            (b'~', 6) => Some(KeyCode::PageDown),
+            (b'~', 11..=15) => KeyCode::from_function(number - 10),
+            (b'~', 17..=21) => KeyCode::from_function(number - 11),
+            (b'~', 23..=24) => KeyCode::from_function(number - 12),
+            _ => None,
+        }
+        .ok_or_else(unsupported)?;
+
+        // the parameter is `1` plus the modifier bits, as xterm sends
+        let bits = modifier
+            .checked_sub(1)
+            .filter(|bits| *bits <= 0b111)
+            .ok_or_else(unsupported)?;
+
+        let flags = [
+            (0b001, KeyModifiers::SHIFT),
+            (0b010, KeyModifiers::ALT),
+            (0b100, KeyModifiers::CONTROL),
+        ];
+
+        Ok(flags
+            .into_iter()
+            .filter(|(bit, _)| bits & bit != 0)
+            .fold(Key::named(code), |key, (_, modifier)| {
+                key.with_modifiers(modifier)
+            }))
+    }
+
+    /// Get a human readable description, joining each [Key::describe] with `then`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use viks::Keymap;
+    ///
+    /// # fn main() {
+    /// let keymap = Keymap::new("<leader>wq").unwrap();
+    ///
+    /// assert_eq!(keymap.describe(), "Leader then W then Q");
+    /// # }
+    /// ```
+    pub fn describe(&self) -> String {
+        self.0
+            .iter()
+            .map(|k| k.describe())
+            .collect::<Vec<_>>()
+            .join(" then ")
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+/// Result of [Keymap::matches].
+pub enum MatchResult {
+    /// The input is exactly the keymap.
+    Full,
+    /// The input is a proper prefix of the keymap, keep collecting.
+    Partial,
+    /// The input doesn't match.
+    None,
+}
+
+/// The output re-parses to the same keymap with [Keymap::new], except the keys that need
+/// the [ParseOptions] to parse, see the `Display` of [Key].
+///
+/// The alternate flag `{:#}` is passed to each [Key].
+impl core::fmt::Display for Keymap {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        for key in self.0.iter() {
+            if f.alternate() {
+                write!(f, "{key:#}")?;
+            } else {
+                write!(f, "{key}")?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl core::fmt::Debug for Keymap {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(
+            f,
+            "[{}]",
+            self.0
+                .iter()
+                .map(|k| format!("{k:?}"))
+                .collect::<Vec<_>>()
+                .join(", ")
         )
     }
-}
+}
+
+/// Parse with [Keymap::new].
+///
+/// # Example
+/// ```
+/// use viks::Keymap;
+///
+/// # fn main() {
+/// let keymap = "<leader>w".parse::<Keymap>().unwrap();
+///
+/// assert_eq!(keymap, Keymap::new("<leader>w").unwrap());
+/// # }
+/// ```
+impl core::str::FromStr for Keymap {
+    type Err = Error;
+
+    fn from_str(s: &str) -> core::result::Result<Self, Self::Err> {
+        Keymap::new(s)
+    }
+}
+
+/// Iterate the keys by value.
+///
+/// # Example
+/// ```
+/// use viks::Keymap;
+///
+/// # fn main() {
+/// let keys = Keymap::new("<c-b>jj").unwrap().into_iter().collect::<Vec<_>>();
+///
+/// assert_eq!(keys.len(), 3);
+/// # }
+/// ```
+impl IntoIterator for Keymap {
+    type Item = Key;
+    type IntoIter = alloc::vec::IntoIter<Key>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.into_iter()
+    }
+}
+
+/// Iterate the keys by reference.
+///
+/// # Example
+/// ```
+/// use viks::Keymap;
+///
+/// # fn main() {
+/// let keymap = Keymap::new("<c-b>jj").unwrap();
+/// let mut alpha = 0;
+///
+/// for key in &keymap {
+///     if key.is_alpha() {
+///         alpha += 1;
+///     }
+/// }
+///
+/// assert_eq!(alpha, 3);
+/// # }
+/// ```
+impl<'a> IntoIterator for &'a Keymap {
+    type Item = &'a Key;
+    type IntoIter = core::slice::Iter<'a, Key>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.iter()
+    }
+}
+
+/// Get the key at the index.
+///
+/// # Panics
+///
+/// Panics if the index is out of bounds.
+///
+/// # Example
+/// ```
+/// use viks::{Key, Keymap};
+///
+/// # fn main() {
+/// let keymap = Keymap::new("<c-w>q").unwrap();
+///
+/// assert_eq!(keymap[0], Key::new("<c-w>").unwrap());
+/// # }
+/// ```
+impl core::ops::Index<usize> for Keymap {
+    type Output = Key;
+
+    fn index(&self, index: usize) -> &Self::Output {
+        &self.0[index]
+    }
+}
+
+/// Compare with the keymap parsed by [Keymap::new], the unavailable keymap is not equal.
+///
+/// # Example
+/// ```
+/// use viks::Keymap;
+///
+/// # fn main() {
+/// let keymap = Keymap::new("<cr><leader>w").unwrap();
+///
+/// assert_eq!(keymap, "<enter><space>w");
+/// assert_eq!(keymap, *"<CR><SPACE>w");
+/// assert_ne!(keymap, "<cr>");
+/// assert_ne!(keymap, "<cr");
+/// # }
+/// ```
+impl PartialEq<str> for Keymap {
+    fn eq(&self, other: &str) -> bool {
+        Keymap::new(other).is_ok_and(|keymap| *self == keymap)
+    }
+}
+
+/// Same as `PartialEq<str>`.
+impl PartialEq<&str> for Keymap {
+    fn eq(&self, other: &&str) -> bool {
+        *self == **other
+    }
+}
+
+/// Concatenate the keymaps.
+///
+/// # Example
+/// ```
+/// use viks::{Key, Keymap};
+///
+/// # fn main() {
+/// let prefix = Keymap::new("<c-w>").unwrap();
+///
+/// assert_eq!(prefix.clone() + Keymap::new("q").unwrap(), Keymap::new("<c-w>q").unwrap());
+/// assert_eq!(prefix + Key::new("j").unwrap(), Keymap::new("<c-w>j").unwrap());
+/// # }
+/// ```
+impl core::ops::Add<Keymap> for Keymap {
+    type Output = Keymap;
+
+    fn add(mut self, rhs: Keymap) -> Self::Output {
+        self += rhs;
+        self
+    }
+}
+
+/// Append the key.
+impl core::ops::Add<Key> for Keymap {
+    type Output = Keymap;
+
+    fn add(mut self, rhs: Key) -> Self::Output {
+        self += rhs;
+        self
+    }
+}
+
+/// Concatenate the keymap in place.
+impl core::ops::AddAssign<Keymap> for Keymap {
+    fn add_assign(&mut self, rhs: Keymap) {
+        self.0.extend(rhs.0);
+    }
+}
+
+/// Append the key in place.
+impl core::ops::AddAssign<Key> for Keymap {
+    fn add_assign(&mut self, rhs: Key) {
+        self.0.push(rhs);
+    }
+}
+
+impl Extend<Key> for Keymap {
+    fn extend<T: IntoIterator<Item = Key>>(&mut self, iter: T) {
+        self.0.extend(iter);
+    }
+}
+
+impl FromIterator<Key> for Keymap {
+    fn from_iter<T: IntoIterator<Item = Key>>(iter: T) -> Self {
+        Keymap(iter.into_iter().collect())
+    }
+}
+
+impl From<Vec<Key>> for Keymap {
+    fn from(value: Vec<Key>) -> Self {
+        Self(value)
+    }
+}
+
+impl From<Key> for Keymap {
+    fn from(value: Key) -> Self {
+        Self(vec![value])
+    }
+}
+
+/// Returns `true` if the buffered tag is only modifiers, e.g. `<c-s-`.
+fn is_modifier_prefix(buf: &str) -> bool {
+    let Some(prefix) = buf.strip_prefix('<').and_then(|b| b.strip_suffix('-')) else {
+        return false;
+    };
+
+    prefix
+        .split('-')
+        .all(|m| KeyModifiers::from_name(m).is_some())
+}
+
+/// Returns `true` if the unclosed tag is only modifiers and `>`, e.g. `<c->` at the end.
+fn is_modifier_only_tag(tag: &str) -> bool {
+    tag.strip_suffix('>').is_some_and(is_modifier_prefix)
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use crate::*;
+
+    #[test]
+    fn new_key() {
+        let key1 = Key::new("A");
+        let key2 = Key::new(";");
+        let key3 = Key::new("<lt>");
+        let key4 = Key::new("<BS>");
+        let key5 = Key::new("<s-a>");
+        let key6 = Key::new("<a-a>");
+        let key7 = Key::new("<c-a>");
+        let key8 = Key::new("<c-CR>");
+        let key9 = Key::new("<leader>");
+
+        assert!(key1.is_ok());
+        assert!(key2.is_ok());
+        assert!(key3.is_ok());
+        assert!(key4.is_ok());
+        assert!(key5.is_ok());
+        assert!(key6.is_ok());
+        assert!(key7.is_ok());
+        assert!(key8.is_ok());
+        assert!(key9.is_ok());
+    }
+
+    #[test]
+    fn modifier_aliases() {
+        assert_eq!(Key::new("<Ctrl-a>").unwrap(), Key::new("<c-a>").unwrap());
+        assert_eq!(Key::new("<Control-a>").unwrap(), Key::new("<c-a>").unwrap());
+        assert_eq!(Key::new("<CONTROL-a>").unwrap(), Key::new("<c-a>").unwrap());
+        assert_eq!(Key::new("<Alt-x>").unwrap(), Key::new("<a-x>").unwrap());
+        assert_eq!(Key::new("<shift-CR>").unwrap(), Key::new("<s-cr>").unwrap());
+
+        assert!(Key::new("<Foo-a>").is_err());
+        assert!(Key::new("<x-a>").is_err());
+    }
+
+    #[test]
+    fn named_keys() {
+        let names = [
+            "enter", "cr", "tab", "esc", "leader", "space", "bs", "del", "lt",
+        ];
+
+        for name in names {
+            let key = Key::new(format!("<{name}>")).unwrap();
+
+            assert_eq!(Key::new(format!("<{}>", name.to_uppercase())).unwrap(), key);
+            assert!(Key::new(format!("<c-{name}>")).unwrap().code == key.code);
+        }
+
+        assert!(Key::new("<entr>").is_err());
+        assert!(Key::new("<1cr>").is_err());
+        assert!(Key::new("<enterenter>").is_err());
+    }
+
+    #[test]
+    fn repeatable_keys() {
+        assert!(Key::new("a").unwrap().is_repeatable());
+        assert!(Key::new("<esc>").unwrap().is_repeatable());
+        assert!(!Key::new("<LeftMouse>").unwrap().is_repeatable());
+        assert!(!Key::new("<c-RightMouse>").unwrap().is_repeatable());
+        assert_eq!(
+            Key::new("<leftmouse>").unwrap().to_string(),
+            "<LeftMouse>".to_string()
+        );
+        assert!(!Key::new("<LeftMouse>").unwrap().is_alpha());
+    }
+
+    #[test]
+    fn combined_modifiers() {
+        let key1 = Key::new("<c-s-a>").unwrap();
+        let key2 = Key::new("<s-c-a>").unwrap();
+        let key3 = Key::new("<c-A>").unwrap();
+        let key4 = Key::new("<C-A-Del>").unwrap();
+        let key5 = Key::new("<ctrl-alt-shift-1>").unwrap();
+
+        assert_eq!(
+            key1,
+            Key {
+                code: KeyCode::A,
+                modifiers: KeyModifiers::CONTROL | KeyModifiers::SHIFT,
+                payload: Payload::NONE,
+                leader: Leader::No,
+            }
+        );
+        assert_eq!(key1, key2);
+        assert_eq!(key1, key3);
+        assert_eq!(key1.to_string(), "<c-s-a>");
+        assert_eq!(key4.to_string(), "<c-a-DEL>");
+        assert_eq!(key5.to_string(), "<c-a-s-1>");
+        assert!(Key::new("<c-x-a>").is_err());
+    }
+
+    #[test]
+    fn modifier_combinations() {
+        for bits in 0u8..8 {
+            let modifiers = KeyModifiers(bits);
+
+            assert_eq!(modifiers.is_shift(), bits & 0b001 != 0);
+            assert_eq!(modifiers.is_ctrl(), bits & 0b010 != 0);
+            assert_eq!(modifiers.is_alt(), bits & 0b100 != 0);
+            assert_eq!(
+                modifiers,
+                [
+                    (modifiers.is_shift(), KeyModifiers::SHIFT),
+                    (modifiers.is_ctrl(), KeyModifiers::CONTROL),
+                    (modifiers.is_alt(), KeyModifiers::ALT),
+                ]
+                .into_iter()
+                .filter(|(set, _)| *set)
+                .fold(KeyModifiers::NONE, |acc, (_, m)| acc | m)
+            );
+        }
+    }
+
+    #[test]
+    fn keycode_from_ascii() {
+        for byte in 0u8..128 {
+            if let Some(code) = KeyCode::from_ascii(byte) {
+                assert_eq!(code as u8, byte);
+                assert_eq!(code.as_ascii(), byte as char);
+            }
+        }
+
+        assert!(KeyCode::from_ascii(0).is_none());
+        assert!(KeyCode::from_ascii(b'a').is_none());
+        assert!(KeyCode::from_ascii(128).is_none());
+    }
+
+    #[test]
+    fn key_try_from() {
+        assert_eq!(Key::try_from('A').unwrap(), Key::new("A").unwrap());
+        assert_eq!(Key::try_from('x').unwrap(), Key::new("x").unwrap());
+        assert_eq!(Key::try_from('\t').unwrap(), Key::new("<tab>").unwrap());
+        assert_eq!(Key::try_from(' ').unwrap(), Key::new("<space>").unwrap());
+        assert_eq!(Key::try_from('\r').unwrap(), Key::new("<cr>").unwrap());
+        assert_eq!(Key::try_from('<').unwrap(), Key::new("<lt>").unwrap());
+        assert_eq!(Key::try_from("<c-a>").unwrap(), Key::new("<c-a>").unwrap());
+        assert_eq!(Key::try_from('é').unwrap_err().kind(), ErrorKind::NotAscii);
+        assert_eq!(
+            Key::try_from('\0').unwrap_err().kind(),
+            ErrorKind::UnknownKey
+        );
+        assert!(Key::try_from("").is_err());
+    }
+
+    #[test]
+    fn key_with_modifiers() {
+        let key = Key::new("a").unwrap();
+
+        assert_eq!(key.with_ctrl(), Key::new("<c-a>").unwrap());
+        assert_eq!(key.with_ctrl().with_ctrl(), Key::new("<c-a>").unwrap());
+        assert_eq!(key.with_shift(), Key::new("A").unwrap());
+        assert_eq!(
+            key.with_alt().with_shift().with_ctrl(),
+            Key::new("<c-a-s-a>").unwrap()
+        );
+        assert_eq!(key.with_ctrl().to_string(), "<c-a>");
+        assert_eq!(Key::new("<F1>").unwrap().with_shift().to_string(), "<s-F1>");
+        assert_eq!(key.with_modifiers(KeyModifiers::NONE), key);
+        assert_eq!(
+            key.with_modifiers(KeyModifiers::ALT | KeyModifiers::CONTROL),
+            Key::new("<c-a-a>").unwrap()
+        );
+    }
+
+    #[test]
+    fn shift_toggle() {
+        let a = Key::new("a").unwrap();
+        let upper_a = Key::new("A").unwrap();
+
+        assert_eq!(a.shifted(), upper_a);
+        assert_eq!(upper_a.unshifted(), a);
+        assert_eq!(upper_a.shifted(), upper_a);
+        assert_eq!(a.unshifted(), a);
+        assert_eq!(
+            Key::new("<c-a-s-x>").unwrap().unshifted(),
+            Key::new("<c-a-x>").unwrap()
+        );
+        assert_eq!(
+            Key::new("<s-1>").unwrap().unshifted(),
+            Key::new("1").unwrap()
+        );
+        assert_eq!(Key::TAB.shifted(), Key::new("<BackTab>").unwrap());
+        assert_eq!(
+            Key::new("<c-s-tab>").unwrap().unshifted(),
+            Key::new("<c-tab>").unwrap()
+        );
+        assert_eq!(Key::new("<F1>").unwrap().shifted().to_string(), "<s-F1>");
+    }
+
+    #[test]
+    fn new_key_bytes() {
+        assert_eq!(Key::new_bytes(b"A").unwrap(), Key::new("A").unwrap());
+        assert_eq!(
+            Key::new_bytes(b"<c-a>").unwrap(),
+            Key::new("<c-a>").unwrap()
+        );
+        assert_eq!(Key::new_bytes(b"<lt>").unwrap(), Key::new("<lt>").unwrap());
+        assert_eq!(Key::new_bytes(b"<BOO>"), Key::new("<BOO>"));
+        assert_eq!(Key::new_bytes(b"").unwrap_err().kind(), ErrorKind::Empty);
+        assert_eq!(
+            Key::new_bytes("é".as_bytes()).unwrap_err().kind(),
+            ErrorKind::NotAscii
+        );
+        assert_eq!(
+            Key::new_bytes(b"<c-\x80>").unwrap_err().kind(),
+            ErrorKind::NotAscii
+        );
+    }
+
+    #[test]
+    fn key_from_byte() {
+        let cases = [
+            (0x00, "<c-@>"),
+            (0x01, "<c-a>"),
+            (0x08, "<c-h>"),
+            (0x09, "<tab>"),
+            (0x0a, "<nl>"),
+            (0x0d, "<cr>"),
+            (0x17, "<c-w>"),
+            (0x1a, "<c-z>"),
+            (0x1b, "<esc>"),
+            (0x1c, "<c-\\>"),
+            (0x1f, "<c-_>"),
+            (0x20, "<space>"),
+            (0x3c, "<lt>"),
+            (0x61, "a"),
+            (0x7e, "~"),
+            (0x7f, "<bs>"),
+        ];
+
+        for (b, tag) in cases {
+            assert_eq!(
+                Key::from_byte(b).unwrap(),
+                Key::new(tag).unwrap(),
+                "{b:#04x}"
+            );
+        }
+
+        assert_eq!(
+            Key::from_byte(0x80).unwrap_err().kind(),
+            ErrorKind::NotAscii
+        );
+        assert_eq!(
+            Keymap::from_bytes(b"\x02jj").unwrap(),
+            Keymap::new("<c-b>jj").unwrap()
+        );
+        let err = Keymap::from_bytes(b"ab\xff").unwrap_err();
+
+        assert_eq!(err.kind(), ErrorKind::NotAscii);
+        assert_eq!(err.offset(), Some(2));
+        assert_eq!(err.index(), Some(2));
+    }
+
+    #[test]
+    fn line_feed() {
+        let nl = Key::new("<nl>").unwrap();
+        let ctrl_j = Key::new("<c-j>").unwrap();
+        let ctrl_m = Key::new("<c-m>").unwrap();
+
+        assert_eq!(nl.code(), Code::LineFeed);
+        assert_eq!(nl, Key::new("<NewLine>").unwrap());
+        assert_eq!(nl.to_string(), "<NL>");
+        assert_ne!(nl, Key::ENTER);
+        assert_ne!(ctrl_j, nl);
+        assert_ne!(ctrl_j, Key::ENTER);
+        assert_ne!(ctrl_m, Key::ENTER);
+        assert_eq!(ctrl_j.code(), Code::Char('j'));
+        assert_eq!(ctrl_m.code(), Code::Char('m'));
+        assert_eq!(ctrl_j.to_byte(), nl.to_byte());
+        assert_eq!(ctrl_m.to_byte(), Key::ENTER.to_byte());
+    }
+
+    #[test]
+    fn key_to_byte() {
+        let cases = [
+            ("<c-@>", Some(0x00)),
+            ("<c-a>", Some(0x01)),
+            ("<c-z>", Some(0x1a)),
+            ("<c-_>", Some(0x1f)),
+            ("<c-i>", Some(0x09)),
+            ("a", Some(b'a')),
+            ("A", Some(b'A')),
+            ("<lt>", Some(b'<')),
+            ("<space>", Some(b' ')),
+            ("<cr>", Some(0x0d)),
+            ("<nl>", Some(0x0a)),
+            ("<tab>", Some(0x09)),
+            ("<esc>", Some(0x1b)),
+            ("<bs>", Some(0x7f)),
+            ("<a-a>", None),
+            ("<c-s-a>", None),
+            ("<c-1>", None),
+            ("<c-cr>", None),
+            ("<s-cr>", None),
+            ("<s-1>", None),
+            ("<del>", None),
+            ("<F1>", None),
+        ];
+
+        for (tag, byte) in cases {
+            assert_eq!(Key::new(tag).unwrap().to_byte(), byte, "{tag}");
+        }
+
+        for b in 0..0x80 {
+            let key = Key::from_byte(b).unwrap();
+
+            assert_eq!(key.to_byte(), Some(b), "{b:#04x}");
+        }
+    }
+
+    #[test]
+    fn char_code_keys() {
+        let key1 = Key::new("<Char-0x41>").unwrap();
+        let key2 = Key::new("<char-97>").unwrap();
+
+        assert_eq!(key1.code(), Key::new("A").unwrap().code());
+        assert_eq!(key1, Key::new("A").unwrap());
+        assert_eq!(key2, Key::new("a").unwrap());
+        assert_eq!(Key::new("<Char-0X3c>").unwrap(), Key::new("<lt>").unwrap());
+        assert_eq!(Key::new("<Char-9>").unwrap(), Key::new("<tab>").unwrap());
+        assert_eq!(Key::new("<c-Char-97>").unwrap(), Key::new("<c-a>").unwrap());
+        assert_eq!(
+            Keymap::new("<Char-0x41>b").unwrap(),
+            Keymap::new("Ab").unwrap()
+        );
+        assert_eq!(
+            Key::new("<Char-128>").unwrap_err().kind(),
+            ErrorKind::UnknownKey
+        );
+        assert_eq!(
+            Key::new("<Char-0x110000>").unwrap_err().kind(),
+            ErrorKind::UnknownKey
+        );
+        assert_eq!(
+            Key::new("<Char-1>").unwrap_err().kind(),
+            ErrorKind::UnknownKey
+        );
+        assert_eq!(
+            Key::new("<Char-0xzz>").unwrap_err().kind(),
+            ErrorKind::InvalidFormat
+        );
+        assert_eq!(
+            Key::new("<Char-->").unwrap_err().kind(),
+            ErrorKind::InvalidFormat
+        );
+    }
+
+    #[test]
+    fn unicode_keys() {
+        let options = ParseOptions {
+            unicode: true,
+            ..Default::default()
+        };
+
+        let key1 = Key::new_with_options("é", &options).unwrap();
+        let key2 = Key::new_with_options("<c-あ>", &options).unwrap();
+        let keymap = Keymap::new_with_options("aé<c-あ><cr>", &options).unwrap();
+
+        assert_eq!(key1.code(), Code::Char('é'));
+        assert_eq!(key1.char(), Some('é'));
+        assert_eq!(key1.to_string(), "é");
+        assert_eq!(key1.to_byte(), None);
+        assert_eq!(key2.code(), Code::Char('あ'));
+        assert!(key2.modifiers().is_ctrl());
+        assert_eq!(key2.to_string(), "<c-あ>");
+        assert_eq!(key2.describe(), "Ctrl+あ");
+        assert_ne!(key1, Key::new_with_options("è", &options).unwrap());
+        assert_ne!(key1, Key::new("e").unwrap());
+        assert_eq!(
+            Key::new_with_options("a", &options).unwrap(),
+            Key::new("a").unwrap()
+        );
+        assert_eq!(keymap.len(), 4);
+        assert_eq!(keymap[1], key1);
+        assert_eq!(keymap[2], key2);
+        assert_eq!(
+            Keymap::new_with_options(&keymap.to_string(), &options).unwrap(),
+            keymap
+        );
+        assert_eq!(Key::try_from(&KeyToken::from(key2)).unwrap(), key2);
+        assert_eq!(format!("{key1:?}"), "Key { code: 'é', modifiers: 0b000 }");
+        assert!(Key::new_with_options("éé", &options).is_err());
+
+        assert_eq!(Key::new("é").unwrap_err().kind(), ErrorKind::NotAscii);
+        assert_eq!(Key::new("<c-あ>").unwrap_err().kind(), ErrorKind::NotAscii);
+        assert_eq!(Keymap::new("aé").unwrap_err().kind(), ErrorKind::NotAscii);
+    }
+
+    #[test]
+    fn key_eq_str() {
+        let key = Key::new("<s-a>").unwrap();
+
+        assert!(key == "A");
+        assert!(key == "<shift-a>");
+        assert!(key == *"<S-A>");
+        assert!(key != "a");
+        assert!(key != "");
+        assert!(key != "<boo>");
+        assert!(Key::new("<cr>").unwrap() == "<Enter>");
+    }
+
+    #[test]
+    fn back_tab() {
+        let key1 = Key::new("<s-tab>").unwrap();
+        let key2 = Key::new("<BackTab>").unwrap();
+
+        assert_eq!(key1, key2);
+        assert_eq!(key1, Key::new("<S-Tab>").unwrap());
+        assert_eq!(key1, Key::new("<s-backtab>").unwrap());
+        assert_eq!(key1, Key::new("<tab>").unwrap().with_shift());
+        assert_eq!(key1.code(), Code::BackTab);
+        assert!(!key1.modifiers().is_shift());
+        assert_eq!(key1.to_string(), "<BackTab>");
+        assert_eq!(Key::new("<c-s-tab>").unwrap().to_string(), "<c-BackTab>");
+        assert_ne!(key1, Key::new("<tab>").unwrap());
+        assert_eq!(key1.to_byte(), None);
+    }
+
+    #[test]
+    fn keymap_chars_same_as_key() {
+        for c in (0..128).filter_map(char::from_u32).chain(['é', 'あ']) {
+            let s = c.to_string();
+
+            if c == '<' {
+                continue;
+            }
+
+            match (Keymap::new(&s), Key::new(&s)) {
+                (Ok(keymap), Ok(key)) => assert_eq!(keymap, Keymap::from(vec![key])),
+                (Err(e1), Err(e2)) => {
+                    assert_eq!(e1.kind(), e2.kind());
+                    assert_eq!(e1.format(), e2.format());
+                }
+                _ => panic!("{c:?}"),
+            }
+        }
+    }
+
+    #[test]
+    fn key_modifiers_list() {
+        use Modifier::*;
+
+        let cases: [(&str, &[Modifier]); 6] = [
+            ("a", &[]),
+            ("A", &[Shift]),
+            ("<c-s-a>", &[Ctrl, Shift]),
+            ("<s-c-a>", &[Ctrl, Shift]),
+            ("<shift-alt-ctrl-cr>", &[Ctrl, Alt, Shift]),
+            ("<a-F1>", &[Alt]),
+        ];
+
+        for (tag, modifiers) in cases {
+            assert_eq!(Key::new(tag).unwrap().modifiers_list(), modifiers, "{tag}");
+        }
+    }
+
+    #[test]
+    fn function_keys() {
+        let key1 = Key::new("<F1>").unwrap();
+        let key2 = Key::new("<f12>").unwrap();
+        let key3 = Key::new("<c-f3>").unwrap();
+        let key4 = Key::new("<F24>").unwrap();
+
+        assert_eq!(key1.to_string(), "<F1>");
+        assert_eq!(key2.to_string(), "<F12>");
+        assert_eq!(key3.to_string(), "<c-F3>");
+        assert_eq!(key4.to_string(), "<F24>");
+        assert!(key3.modifiers.is_ctrl());
+        assert!(key1.code.is_function() && key4.code.is_function());
+        assert!(!key1.is_alpha() && !key1.is_digit());
+        assert_ne!(key1, Key::new("<F2>").unwrap());
+        assert!(Key::new("<F25>").is_err());
+        assert!(Key::new("<F0>").is_err());
+
+        for n in 1..=24 {
+            let code = KeyCode::from_function(n).unwrap();
+
+            assert_eq!(Key::named(code), Key::new(format!("<F{n}>")).unwrap());
+        }
+
+        assert!(KeyCode::from_function(0).is_none());
+        assert!(KeyCode::from_function(25).is_none());
+    }
+
+    #[test]
+    fn keypad_keys() {
+        let key1 = Key::new("<k1>").unwrap();
+        let key2 = Key::new("<kPlus>").unwrap();
+
+        assert_eq!(key1, Key::new("<K1>").unwrap());
+        assert_eq!(key2, Key::new("<KPLUS>").unwrap());
+        assert_eq!(key1.to_string(), "<k1>");
+        assert_eq!(key2.to_string(), "<kPlus>");
+        assert_eq!(Key::new("<c-kEnter>").unwrap().to_string(), "<c-kEnter>");
+        assert_eq!(key1.code(), Code::Keypad(1));
+        assert_eq!(Key::new("<kDivide>").unwrap().code(), Code::KeypadDivide);
+        assert_ne!(key1, Key::new("1").unwrap());
+        assert_ne!(key2, Key::new("+").unwrap());
+        assert_ne!(Key::new("<kMinus>").unwrap(), Key::new("-").unwrap());
+        assert_ne!(Key::new("<kMultiply>").unwrap(), Key::new("*").unwrap());
+        assert_ne!(Key::new("<kEnter>").unwrap(), Key::new("<CR>").unwrap());
+        assert!(!key1.is_digit());
+        assert_eq!(key1.char(), None);
+        assert!(Key::new("<k10>").is_err());
+    }
+
+    #[test]
+    fn arrow_keys() {
+        let key1 = Key::new("<up>").unwrap();
+        let key2 = Key::new("<c-left>").unwrap();
+
+        assert_eq!(key1, Key::new("<Up>").unwrap());
+        assert_eq!(key1, Key::new("<UP>").unwrap());
+        assert_eq!(key1.to_string(), "<Up>");
+        assert_eq!(key2.to_string(), "<c-Left>");
+        assert_eq!(Key::new("<Down>").unwrap().to_string(), "<Down>");
+        assert_eq!(Key::new("<RIGHT>").unwrap().to_string(), "<Right>");
+        assert!(key2.modifiers.is_ctrl());
+        assert_ne!(key1, Key::new("<Down>").unwrap());
+    }
+
+    #[test]
+    fn navigation_keys() {
+        for name in ["Home", "End", "PageUp", "PageDown", "Insert"] {
+            let key = Key::new(format!("<{name}>")).unwrap();
+
+            assert_eq!(key.to_string(), format!("<{name}>"));
+            assert_eq!(Key::new(format!("<{}>", name.to_lowercase())).unwrap(), key);
+        }
+
+        let key = Key::new("<c-home>").unwrap();
+
+        assert!(key.modifiers.is_ctrl());
+        assert_eq!(key.to_string(), "<c-Home>");
+        assert_eq!(Key::new("<kHome>").unwrap(), Key::new("<Home>").unwrap());
+        assert_eq!(Key::new("<kEnd>").unwrap(), Key::new("<End>").unwrap());
+    }
+
+    #[test]
+    fn pseudo_keys() {
+        let key1 = Key::new("<Nop>").unwrap();
+        let key2 = Key::new("<plug>").unwrap();
+        let keys = Keymap::new("<Plug>(MyThing)").unwrap();
+
+        assert_eq!(key1.to_string(), "<Nop>");
+        assert_eq!(key2.to_string(), "<Plug>");
+        assert_eq!(key1.code(), Code::Nop);
+        assert!(!key1.is_repeatable() && !key2.is_repeatable());
+        assert_eq!(keys[0], key2);
+        assert_eq!(keys.len(), 10);
+        assert_eq!(keys.to_string(), "<Plug>(MyThing)");
+        assert_eq!(Keymap::new(keys.to_string()).unwrap(), keys);
+    }
+
+    #[test]
+    fn bar_bslash_keys() {
+        assert_eq!(Key::new("<Bar>").unwrap(), Key::new("|").unwrap());
+        assert_eq!(Key::new("<bslash>").unwrap(), Key::new("\\").unwrap());
+        assert_eq!(Key::new("<c-Bslash>").unwrap(), Key::new("<c-\\>").unwrap());
+        assert_eq!(
+            Keymap::new(":echo<space>1<Bar>echo<space>2<CR>").unwrap(),
+            Keymap::new(":echo<space>1|echo<space>2<CR>").unwrap()
+        );
+    }
+
+    #[test]
+    fn eq_keys() {
+        let a_key = Key::new("A").unwrap();
+        let a_s_key = Key::new("A").unwrap();
+        let a_a_key = Key::new("<s-a>").unwrap();
+        let a_a_b_key = Key::new("<s-A>").unwrap();
+
+        assert_eq!(a_key, a_s_key);
+        assert_eq!(a_key, a_a_key);
+        assert_eq!(a_a_key, a_a_b_key);
+    }
+
+    #[test]
+    fn hash_keys() {
+        use std::collections::HashMap;
+
+        let mut map = HashMap::new();
+
+        map.insert(Key::new("A").unwrap(), "upper");
+        map.insert(Key::new("<c-A>").unwrap(), "ctrl upper");
+        map.insert(Key::new("<cr>").unwrap(), "enter");
+
+        assert_eq!(map.get(&Key::new("<s-a>").unwrap()), Some(&"upper"));
+        assert_eq!(map.get(&Key::new("<S-A>").unwrap()), Some(&"upper"));
+        assert_eq!(map.get(&Key::new("<s-c-a>").unwrap()), Some(&"ctrl upper"));
+        assert_eq!(map.get(&Key::new("<Enter>").unwrap()), Some(&"enter"));
+        assert_eq!(map.get(&Key::new("a").unwrap()), None);
+    }
+
+    #[test]
+    fn invalid_key() {
+        let key1 = Key::new("");
+        let key2 = Key::new("ÿ");
+        let key3 = Key::new("aa");
+        let key4 = Key::new("<BOO>");
+        let key5 = Key::new("<B");
+        let key6 = Key::new(" ");
+
+        assert!(key1.is_err());
+        assert!(key2.is_err());
+        assert!(key3.is_err());
+        assert!(key4.is_err());
+        assert!(key5.is_err());
+        assert!(key6.is_err());
+    }
+
+    #[test]
+    fn suggest_key_name() {
+        let err1 = Key::new("<entre>").unwrap_err();
+        let err2 = Key::new("<c-ESX>").unwrap_err();
+        let err3 = Key::new("<HappyNewYear>").unwrap_err();
+        let err4 = Key::new("<xy>").unwrap_err();
+
+        assert_eq!(err1.suggestion(), Some("enter"));
+        assert_eq!(err1.to_string(), "unknown key, did you mean `<enter>`?");
+        assert_eq!(err2.suggestion(), Some("esc"));
+        assert_eq!(err3.suggestion(), None);
+        assert_eq!(err4.suggestion(), None);
+    }
+
+    #[test]
+    fn error_kind() {
+        assert_eq!(Key::new("").unwrap_err().kind(), ErrorKind::Empty);
+        assert_eq!(Key::new("ÿ").unwrap_err().kind(), ErrorKind::NotAscii);
+        assert_eq!(Key::new("aa").unwrap_err().kind(), ErrorKind::InvalidFormat);
+        assert_eq!(Key::new("<BOO>").unwrap_err().kind(), ErrorKind::UnknownKey);
+        assert_eq!(Key::new("<B").unwrap_err().kind(), ErrorKind::InvalidFormat);
+        assert_eq!(Key::new(" ").unwrap_err().kind(), ErrorKind::UnknownKey);
+        assert_eq!(
+            Key::new("<x-a>").unwrap_err().kind(),
+            ErrorKind::InvalidModifier
+        );
+        assert_eq!(
+            Keymap::new("<leader").unwrap_err().kind(),
+            ErrorKind::UnclosedTag
+        );
+        assert_eq!(Key::new("<BOO>").unwrap_err().format(), "<BOO>");
+    }
+
+    #[test]
+    fn nested_tag() {
+        let err1 = Keymap::new("<<c-a>>").unwrap_err();
+        let err2 = Keymap::new("ab<cr<c-a>>").unwrap_err();
+
+        assert_eq!(err1.kind(), ErrorKind::NestedTag);
+        assert_eq!(err1.offset(), Some(1));
+        assert_eq!(err2.kind(), ErrorKind::NestedTag);
+        assert_eq!(err2.offset(), Some(5));
+        assert_ne!(
+            Keymap::new("<c-<>")
+                .map(|keys| keys.len())
+                .map_err(|e| e.kind()),
+            Err(ErrorKind::NestedTag)
+        );
+    }
+
+    #[test]
+    fn empty_modifier_tags() {
+        assert_eq!(Key::new("<->").unwrap(), Key::new("-").unwrap());
+        assert_eq!(
+            Key::new("<c-->").unwrap(),
+            Key::new("-").unwrap().with_ctrl()
+        );
+        assert_eq!(Key::new("<c->").unwrap_err().kind(), ErrorKind::MissingKey);
+        assert_eq!(Key::new("<s->").unwrap_err().kind(), ErrorKind::MissingKey);
+        assert_eq!(
+            Key::new("<c-a->").unwrap_err().kind(),
+            ErrorKind::MissingKey
+        );
+        assert_eq!(
+            Key::new("<-a>").unwrap_err().kind(),
+            ErrorKind::InvalidModifier
+        );
+        assert_eq!(
+            Key::new("<c--a>").unwrap_err().kind(),
+            ErrorKind::InvalidModifier
+        );
+        assert_eq!(
+            Key::new("<c->").unwrap_err().to_string(),
+            "modifier has no key"
+        );
+    }
+
+    #[test]
+    fn keys_with_modifier() {
+        let keymap = Keymap::new("<c-a>b<c-c>").unwrap();
+
+        assert_eq!(
+            keymap.keys_with_modifier(Modifier::Ctrl),
+            vec![&Key::new("<c-a>").unwrap(), &Key::new("<c-c>").unwrap()]
+        );
+        assert!(keymap.keys_with_modifier(Modifier::Alt).is_empty());
+    }
+
+    #[test]
+    fn modifier_only_tags() {
+        for prefix in ["c-", "a-", "s-", "c-s-", "ctrl-alt-", "shift-"] {
+            assert_eq!(
+                Key::new(format!("<{prefix}>")).unwrap_err().kind(),
+                ErrorKind::MissingKey,
+                "<{prefix}>"
+            );
+        }
+
+        for tag in [
+            "<c-a>", "<c-s-cr>", "<a-->", "<s-tab>", "<c-F12>", "<leader>", "<lt>",
+        ] {
+            assert!(!Key::new(tag).unwrap().is_modifier_only(), "{tag}");
+        }
+
+        // `>` after the modifier is the key in Keymap, e.g. `<c->>`, but not at the end
+        let err = Keymap::new("a<c-s->").unwrap_err();
+
+        assert_eq!(err.kind(), ErrorKind::MissingKey);
+        assert_eq!(err.offset(), Some(1));
+        assert_eq!(err.index(), Some(1));
+        assert_eq!(
+            Keymap::new("a<c-s-").unwrap_err().kind(),
+            ErrorKind::UnclosedTag
+        );
+        assert_eq!(
+            Keymap::new("a<c->>").unwrap()[1],
+            Key::new("<c->>").unwrap()
+        );
+    }
+
+    #[test]
+    fn keymap_from_keys() {
+        let key = Key::new("<c-w>").unwrap();
+        let keys = Keymap::new("<c-w>jk").unwrap();
+
+        assert_eq!(Keymap::from(key), Keymap::new("<c-w>").unwrap());
+        assert_eq!(keys.iter().copied().collect::<Keymap>(), keys);
+        assert_eq!(
+            ["g", "<lt>", "<CR>"]
+                .into_iter()
+                .map(|s| Key::new(s).unwrap())
+                .collect::<Keymap>(),
+            Keymap::new("g<lt><CR>").unwrap()
+        );
+    }
+
+    #[test]
+    fn stray_gt() {
+        let keys = Keymap::new("a>b").unwrap();
+
+        assert_eq!(
+            keys,
+            Keymap(vec![
+                Key::new("a").unwrap(),
+                Key::new(">").unwrap(),
+                Key::new("b").unwrap(),
+            ])
+        );
+        assert_eq!(Keymap::new("<lt>>").unwrap().len(), 2);
+    }
+
+    #[test]
+    fn eq_errors() {
+        let err1 = Keymap::new("ab<BOO>").unwrap_err();
+        let err2 = Keymap::new("ab<BOO>").unwrap_err();
+
+        assert_eq!(err1, err2);
+        assert_eq!(err1.clone(), err2);
+        assert_ne!(err1, Keymap::new("a<BOO>").unwrap_err());
+        assert_ne!(err1, Keymap::new("ab<BO>").unwrap_err());
+        assert_eq!(Key::new("<x-a>"), Key::new("<x-a>"));
+    }
+
+    #[test]
+    fn error_offset() {
+        assert_eq!(Keymap::new("ab<leader").unwrap_err().offset(), Some(2));
+        assert_eq!(Keymap::new("<cr>a<<c-a>").unwrap_err().offset(), Some(6));
+        assert_eq!(Keymap::new("ab<BOO>cd").unwrap_err().offset(), Some(2));
+        assert_eq!(Keymap::new("ab cd").unwrap_err().offset(), Some(2));
+        assert_eq!(Key::new("<BOO>").unwrap_err().offset(), None);
+    }
+
+    #[test]
+    fn dedup_keymaps() {
+        let keymaps = ["gg", "<lt>", "gg"]
+            .into_iter()
+            .map(|s| Keymap::new(s).unwrap())
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            Keymap::dedup(keymaps),
+            vec![Keymap::new("gg").unwrap(), Keymap::new("<lt>").unwrap()]
+        );
+        assert!(Keymap::dedup(vec![]).is_empty());
+
+        let keymaps = Keymap::parse_sequence("gg gg <lt> <LT> gg <Enter> <cr>").unwrap();
+
+        assert_eq!(
+            Keymap::dedup_consecutive(keymaps),
+            Keymap::parse_sequence("gg <lt> gg <cr>").unwrap()
+        );
+        assert!(Keymap::dedup_consecutive(vec![]).is_empty());
+    }
+
+    #[test]
+    fn error_index() {
+        let err = Keymap::new("ab<BOO>cd").unwrap_err();
+
+        assert_eq!(err.index(), Some(2));
+        assert_eq!(err.offset(), Some(2));
+        assert_eq!(err.format(), "<BOO>");
+        assert_eq!(Keymap::new("<cr><c-w>é").unwrap_err().index(), Some(2));
+        assert_eq!(Keymap::new("<cr>a<<c-a>").unwrap_err().index(), Some(2));
+        assert_eq!(Keymap::new("ab<leader").unwrap_err().index(), Some(2));
+        assert_eq!(Key::new("<BOO>").unwrap_err().index(), None);
+    }
+
+    #[test]
+    fn new_keymap() {
+        let keys1 = Keymap::new("NewYonk");
+        let keys2 = Keymap::new("<s-Z>Z");
+        let keys3 = Keymap::new("<lt>HappyNewYear>");
+        let keys4 = Keymap::new("<a-~><c-#><s-&>");
+        let keys5 = Keymap::new("<leader><cr>");
+
+        assert!(keys1.is_ok());
+        assert!(keys2.is_ok());
+        assert!(keys3.is_ok());
+        assert!(keys4.is_ok());
+        assert!(keys5.is_ok());
+    }
+
+    #[test]
+    fn literal_unknown_tags() {
+        let options = ParseOptions {
+            literal_unknown_tags: true,
+            ..Default::default()
+        };
+
+        let keys1 = Keymap::new_with_options("<abc>", &options).unwrap();
+        let keys2 = Keymap::new_with_options("<cr><abc><c-a>", &options).unwrap();
+
+        assert_eq!(keys1.as_vec().len(), 3);
+        assert_eq!(keys1, Keymap::new("abc").unwrap());
+        assert_eq!(keys2, Keymap::new("<cr>abc<c-a>").unwrap());
+        assert!(Keymap::new("<abc>").is_err());
+        assert!(Keymap::new_with_options("<a b>", &options).is_err());
+    }
+
+    #[test]
+    fn keymap_from_escape_sequence() {
+        let cases: &[(&[u8], &str)] = &[
+            (b"\x1b[A", "<Up>"),
+            (b"\x1b[1;5A", "<c-up>"),
+            (b"\x1bOB", "<Down>"),
+            (b"\x1b[1;3C", "<a-right>"),
+            (b"\x1b[H", "<Home>"),
+            (b"\x1b[4~", "<End>"),
+            (b"\x1b[3;6~", "<c-s-del>"),
+            (b"\x1b[5~", "<PageUp>"),
+            (b"\x1bOP", "<F1>"),
+            (b"\x1b[1;2S", "<s-F4>"),
+            (b"\x1b[24~", "<F12>"),
+            (b"\x1b[Z", "<BackTab>"),
+            (b"\x1bx", "<a-x>"),
+            (b"\x1b", "<Esc>"),
+        ];
+
+        for (seq, tag) in cases {
+            assert_eq!(
+                Keymap::from_escape_sequence(seq).unwrap(),
+                Key::new(tag).unwrap()
+            );
+        }
+
+        assert_eq!(
+            Keymap::from_escape_sequence(b"").unwrap_err().kind(),
+            ErrorKind::Empty
+        );
+
+        for seq in [
+            &b"\x1b[X"[..],
+            b"\x1b[1;9A",
+            b"\x1b[99~",
+            b"\x1b[1;2;3A",
+            b"a\x1b",
+        ] {
+            assert_eq!(
+                Keymap::from_escape_sequence(seq).unwrap_err().kind(),
+                ErrorKind::UnknownKey
+            );
+        }
+    }
+
+    #[test]
+    fn key_from_parts() {
+        assert_eq!(
+            Key::from_parts("a", false, true, false).unwrap(),
+            Key::new("<c-a>").unwrap()
+        );
+        assert_eq!(
+            Key::from_parts("CR", false, false, false).unwrap(),
+            Key::new("<CR>").unwrap()
+        );
+        assert_eq!(
+            Key::from_parts("tab", true, false, false).unwrap(),
+            Key::new("<s-tab>").unwrap()
+        );
+        assert_eq!(
+            Key::from_parts("A", false, true, true).unwrap(),
+            Key::new("<c-a-s-a>").unwrap()
+        );
+        assert_eq!(
+            Key::from_parts("<CR>", false, false, false)
+                .unwrap_err()
+                .kind(),
+            ErrorKind::UnknownKey
+        );
+        assert_eq!(
+            Key::from_parts("boo", false, true, false)
+                .unwrap_err()
+                .kind(),
+            ErrorKind::UnknownKey
+        );
+    }
+
+    #[test]
+    fn lenient_unknown_tags() {
+        let options = ParseOptions {
+            strict: false,
+            ..Default::default()
+        };
+
+        assert_eq!(Key::new("<BOO>").unwrap_err().kind(), ErrorKind::UnknownKey);
+
+        let key = Key::new_with_options("<BOO>", &options).unwrap();
+
+        assert_eq!(key.code(), Code::Unknown("BOO"));
+        assert_eq!(key.to_string(), "<BOO>");
+        assert_eq!(format!("{key:#}"), "<BOO>");
+        assert_eq!(
+            Key::new_with_options(&key.to_string(), &options).unwrap(),
+            key
+        );
+        assert_ne!(key, Key::new_with_options("<FOO>", &options).unwrap());
+        assert_ne!(key, Key::new_with_options("<boo>", &options).unwrap());
+        assert!(key > Key::new_with_options("<AOO>", &options).unwrap());
+        assert!(!key.is_special());
+        assert_eq!(key.describe(), "BOO");
+        assert!(core::mem::size_of::<Key>() <= 8);
+
+        let keymap = Keymap::new_with_options("j<c-BOO><CR>", &options).unwrap();
+
+        assert_eq!(keymap.len(), 3);
+        assert_eq!(keymap[1].code(), Code::Unknown("BOO"));
+        assert!(keymap[1].is_control());
+        assert_eq!(keymap.to_string(), "j<c-BOO><CR>");
+        assert_eq!(
+            Keymap::new_with_options(&keymap.to_string(), &options).unwrap(),
+            keymap
+        );
+        assert_ne!(
+            keymap,
+            Keymap::new_with_options("j<c-FOO><CR>", &options).unwrap()
+        );
+        assert!(Keymap::new("j<c-BOO><CR>").is_err());
+        assert_eq!(keymap.to_tokens()[1].name, "BOO");
+        assert!(Keymap::from_tokens(&keymap.to_tokens()).is_err());
+        assert_eq!(
+            Key::new_with_options("<x-a>", &options).unwrap_err().kind(),
+            ErrorKind::InvalidModifier
+        );
+        assert!(Key::new_with_options("<c->", &options).is_err());
+    }
+
+    #[test]
+    fn custom_leader() {
+        let options = ParseOptions {
+            leader: Key::new(",").unwrap(),
+            ..Default::default()
+        };
+
+        let keys1 = Keymap::new_with_options("<leader>w", &options).unwrap();
+        let keys2 = Keymap::new_with_options("<c-leader><LEADER>", &options).unwrap();
+
+        assert_eq!(keys1, Keymap::new(",w").unwrap());
+        assert_eq!(keys2, Keymap::new("<c-,>,").unwrap());
+        assert_eq!(
+            Keymap::new("<leader>w").unwrap(),
+            Keymap::new("<space>w").unwrap()
+        );
+        assert_eq!(
+            Key::new_with_options("<leader>", &options).unwrap(),
+            Key::new(",").unwrap()
+        );
+    }
 
-impl std::fmt::Debug for Keymap {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(
-            f,
-            "[{}]",
-            self.0
-                .iter()
-                .map(|k| format!("{k:?}"))
-                .collect::<Vec<_>>()
-                .join(", ")
-        )
+    #[test]
+    fn starts_with_keymap() {
+        let keymap = Keymap::new("gg").unwrap();
+
+        assert!(keymap.starts_with(&Keymap::new("g").unwrap()));
+        assert!(keymap.starts_with(&Keymap::new("gg").unwrap()));
+        assert!(keymap.starts_with(&Keymap::new("").unwrap()));
+        assert!(!keymap.starts_with(&Keymap::new("gd").unwrap()));
+        assert!(!keymap.starts_with(&Keymap::new("ggg").unwrap()));
+        assert!(
+            Keymap::new("<s-z>Z")
+                .unwrap()
+                .starts_with(&Keymap::new("Z").unwrap())
+        );
     }
-}
 
-impl From<Vec<Key>> for Keymap {
-    fn from(value: Vec<Key>) -> Self {
-        Self(value)
+    #[test]
+    fn strip_prefix_keymap() {
+        let keymap = Keymap::new("gg").unwrap();
+
+        assert_eq!(
+            keymap.strip_prefix(&Keymap::new("g").unwrap()),
+            Some(Keymap::new("g").unwrap())
+        );
+        assert_eq!(
+            keymap.strip_prefix(&Keymap::new("gg").unwrap()),
+            Some(Keymap::new("").unwrap())
+        );
+        assert_eq!(
+            keymap.strip_prefix(&Keymap::new("").unwrap()),
+            Some(keymap.clone())
+        );
+        assert_eq!(keymap.strip_prefix(&Keymap::new("gd").unwrap()), None);
+        assert_eq!(keymap.strip_prefix(&Keymap::new("ggg").unwrap()), None);
+        assert_eq!(
+            Keymap::new("<s-z>Zj")
+                .unwrap()
+                .strip_prefix(&Keymap::new("ZZ").unwrap()),
+            Some(Keymap::new("j").unwrap())
+        );
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use crate::*;
+    #[test]
+    fn validate_same_as_new() {
+        let corpus = [
+            "",
+            "ZZ",
+            "<s-z>Z",
+            "<leader>w<CR>",
+            "<c->>a<a->><->ZZ<leader>w<lt><s-->",
+            "<c-w><Up><F12><k0><kPlus><BackTab><NL>",
+            "<Char-0x41><Char-97>",
+            "a>b",
+            "<c-<>",
+            "ab<leader",
+            "<cr>a<<c-a>",
+            "ab<BOO>cd",
+            "ab cd",
+            "<x-a>",
+            "é",
+            "<<c-a>>",
+            "<Char-0xZZ>",
+        ];
+
+        for s in corpus {
+            assert_eq!(Keymap::validate(s), Keymap::new(s).map(|_| ()), "{s}");
+            assert_eq!(Key::validate(s), Key::new(s).map(|_| ()), "{s}");
+        }
+    }
 
     #[test]
-    fn new_key() {
-        let key1 = Key::new("A");
-        let key2 = Key::new(";");
-        let key3 = Key::new("<lt>");
-        let key4 = Key::new("<BS>");
-        let key5 = Key::new("<s-a>");
-        let key6 = Key::new("<a-a>");
-        let key7 = Key::new("<c-a>");
-        let key8 = Key::new("<c-CR>");
-        let key9 = Key::new("<leader>");
+    fn parse_keymap_sequence() {
+        let keymaps = Keymap::parse_sequence("gg G dd").unwrap();
+        let err = Keymap::parse_sequence("gg\tG <BOO>").unwrap_err();
 
-        assert!(key1.is_ok());
-        assert!(key2.is_ok());
-        assert!(key3.is_ok());
-        assert!(key4.is_ok());
-        assert!(key5.is_ok());
-        assert!(key6.is_ok());
-        assert!(key7.is_ok());
-        assert!(key8.is_ok());
-        assert!(key9.is_ok());
+        assert_eq!(
+            keymaps,
+            vec![
+                Keymap::new("gg").unwrap(),
+                Keymap::new("G").unwrap(),
+                Keymap::new("dd").unwrap(),
+            ]
+        );
+        assert_eq!(
+            Keymap::parse_sequence("  <c-w><space>\n<leader>j ").unwrap(),
+            vec![
+                Keymap::new("<c-w><space>").unwrap(),
+                Keymap::new("<space>j").unwrap(),
+            ]
+        );
+        assert!(Keymap::parse_sequence("").unwrap().is_empty());
+        assert_eq!(err.kind(), ErrorKind::UnknownKey);
+        assert_eq!(err.offset(), Some(5));
+        assert_eq!(
+            Keymap::parse_sequence("a <c-a").unwrap_err().kind(),
+            ErrorKind::UnclosedTag
+        );
     }
 
     #[test]
-    fn eq_keys() {
-        let a_key = Key::new("A").unwrap();
-        let a_s_key = Key::new("A").unwrap();
-        let a_a_key = Key::new("<s-a>").unwrap();
-        let a_a_b_key = Key::new("<s-A>").unwrap();
+    fn contains_key() {
+        let keymap = Keymap::new("<s-a><Esc>j").unwrap();
 
-        assert_eq!(a_key, a_s_key);
-        assert_eq!(a_key, a_a_key);
-        assert_eq!(a_a_key, a_a_b_key);
+        assert!(keymap.contains(&Key::new("A").unwrap()));
+        assert!(keymap.contains(&Key::new("<esc>").unwrap()));
+        assert!(!keymap.contains(&Key::new("a").unwrap()));
+        assert!(!keymap.contains(&Key::new("<c-j>").unwrap()));
+        assert!(!Keymap::new("").unwrap().contains(&Key::ESC));
     }
 
     #[test]
-    fn invalid_key() {
-        let key1 = Key::new("");
-        let key2 = Key::new("ÿ");
-        let key3 = Key::new("aa");
-        let key4 = Key::new("<BOO>");
-        let key5 = Key::new("<B");
-        let key6 = Key::new(" ");
+    fn replace_key_in_keymap() {
+        let leader = Key::new("<leader>").unwrap();
+        let comma = Key::new(",").unwrap();
+        let mut keymap = Keymap::new("<leader>w<leader>q").unwrap();
 
-        assert!(key1.is_err());
-        assert!(key2.is_err());
-        assert!(key3.is_err());
-        assert!(key4.is_err());
-        assert!(key5.is_err());
-        assert!(key6.is_err());
+        assert_eq!(keymap.replace_key(&leader, comma), 2);
+        assert_eq!(keymap, Keymap::new(",w,q").unwrap());
+        assert_eq!(keymap.to_string(), ",w,q");
+        assert_eq!(keymap.replace_key(&leader, comma), 0);
+
+        let mut keymap = Keymap::new("A<s-a>a").unwrap();
+
+        assert_eq!(keymap.replace_key(&Key::new("<S-A>").unwrap(), Key::ESC), 2);
+        assert_eq!(keymap, Keymap::new("<Esc><Esc>a").unwrap());
     }
 
     #[test]
-    fn new_keymap() {
-        let keys1 = Keymap::new("NewYonk");
-        let keys2 = Keymap::new("<s-Z>Z");
-        let keys3 = Keymap::new("<lt>HappyNewYear>");
-        let keys4 = Keymap::new("<a-~><c-#><s-&>");
-        let keys5 = Keymap::new("<leader><cr>");
+    fn split_keymap_at_leader() {
+        let comma = Key::new(",").unwrap();
+        let options = ParseOptions {
+            leader: comma,
+            ..Default::default()
+        };
 
-        assert!(keys1.is_ok());
-        assert!(keys2.is_ok());
-        assert!(keys3.is_ok());
-        assert!(keys4.is_ok());
-        assert!(keys5.is_ok());
+        let keys1 = Keymap::new_with_options("<leader>w", &options).unwrap();
+        let keys2 = Keymap::new_with_options("<leader>", &options).unwrap();
+        let keys3 = Keymap::new_with_options("w<leader>", &options).unwrap();
+
+        assert_eq!(
+            keys1.split_at_leader(&comma),
+            Some((Keymap::new(",").unwrap(), Keymap::new("w").unwrap()))
+        );
+        assert_eq!(
+            keys2.split_at_leader(&comma),
+            Some((Keymap::new(",").unwrap(), Keymap::new("").unwrap()))
+        );
+        assert_eq!(keys3.split_at_leader(&comma), None);
+        assert_eq!(keys1.split_at_leader(&Key::SPACE), None);
+        assert_eq!(Keymap::new("").unwrap().split_at_leader(&comma), None);
+    }
+
+    #[test]
+    fn split_first_keymap() {
+        let mut keymap = Keymap::new("<c-b>jj").unwrap();
+        let mut peeled = vec![];
+
+        while let Some((first, rest)) = keymap.split_first() {
+            peeled.push(*first);
+            keymap = rest;
+        }
+
+        assert!(keymap.is_empty());
+        assert_eq!(Keymap::from(peeled), Keymap::new("<c-b>jj").unwrap());
+        assert!(Keymap::new("").unwrap().split_first().is_none());
+    }
+
+    #[test]
+    fn canonical_keymap() {
+        assert_eq!(
+            Keymap::new("<Enter>").unwrap().canonical(),
+            Keymap::new("<cr>").unwrap().canonical()
+        );
+        assert_eq!(
+            Keymap::new("<s-z>Z<A-S-X>").unwrap().canonical(),
+            Keymap::new("Z<shift-z><alt-X>").unwrap().canonical()
+        );
+        assert_eq!(Keymap::new("<S-C-A>").unwrap().canonical(), "<c-s-a>");
+        assert_eq!(
+            Keymap::new("<leader><space>").unwrap().canonical(),
+            "<SPACE><SPACE>"
+        );
+    }
+
+    #[test]
+    fn add_keymaps() {
+        let mut keymap = Keymap::new("g").unwrap() + Keymap::new("g").unwrap();
+
+        assert_eq!(keymap, Keymap::new("gg").unwrap());
+
+        keymap += Keymap::new("<c-a>").unwrap();
+        keymap += Key::new("A").unwrap();
+
+        assert_eq!(keymap, Keymap::new("gg<c-a>A").unwrap());
+        assert_eq!(keymap.clone() + Keymap::new("").unwrap(), keymap);
+        assert_eq!(
+            Keymap::new("").unwrap() + Key::new("<cr>").unwrap(),
+            Keymap::new("<cr>").unwrap()
+        );
+    }
+
+    #[test]
+    fn match_keymap() {
+        let keymap = Keymap::new("dd").unwrap();
+        let d = Key::new("d").unwrap();
+        let w = Key::new("w").unwrap();
+
+        assert_eq!(keymap.matches(&[]), MatchResult::Partial);
+        assert_eq!(keymap.matches(&[d]), MatchResult::Partial);
+        assert_eq!(keymap.matches(&[d, d]), MatchResult::Full);
+        assert_eq!(keymap.matches(&[d, w]), MatchResult::None);
+        assert_eq!(keymap.matches(&[w]), MatchResult::None);
+        assert_eq!(keymap.matches(&[d, d, d]), MatchResult::None);
+        assert_eq!(Keymap::new("").unwrap().matches(&[]), MatchResult::Full);
+    }
+
+    #[test]
+    fn keymap_eq_str() {
+        let keymap = Keymap::new("<cr>ZZ").unwrap();
+
+        assert!(keymap == "<enter><s-z>Z");
+        assert!(keymap == *"<CR>ZZ");
+        assert!(keymap != "<cr>zz");
+        assert!(keymap != "<cr");
+        assert!(Keymap::new("").unwrap() == "");
+    }
+
+    #[test]
+    fn backslash_escape() {
+        let options = ParseOptions {
+            backslash_escape: true,
+            ..Default::default()
+        };
+
+        let keys1 = Keymap::new_with_options("\\<a", &options).unwrap();
+        let keys2 = Keymap::new_with_options("a\\\\b", &options).unwrap();
+        let keys3 = Keymap::new_with_options("<c-\\>\\<<cr>", &options).unwrap();
+        let err1 = Keymap::new_with_options("ab\\", &options).unwrap_err();
+        let err2 = Keymap::new_with_options("a\\b", &options).unwrap_err();
+
+        assert_eq!(keys1, Keymap::new("<lt>a").unwrap());
+        assert_eq!(keys2, Keymap::new("a\\b").unwrap());
+        assert_eq!(keys3, Keymap::new("<c-\\><lt><cr>").unwrap());
+        assert_eq!(err1.kind(), ErrorKind::InvalidFormat);
+        assert_eq!(err1.offset(), Some(2));
+        assert_eq!(err2.offset(), Some(1));
+        assert_eq!(Keymap::new("a\\b").unwrap().len(), 3);
+        assert!(Keymap::new("\\<a").is_err());
     }
 
     #[test]
@@ -488,6 +3708,25 @@ mod tests {
         assert_eq!(keys1, keys2);
     }
 
+    #[test]
+    fn hash_keymaps() {
+        use std::collections::HashMap;
+        use std::hash::{BuildHasher, RandomState};
+
+        let state = RandomState::new();
+        let keys1 = Keymap::new("<cr>a").unwrap();
+        let keys2 = Keymap::new("<enter>a").unwrap();
+
+        assert_eq!(state.hash_one(&keys1), state.hash_one(&keys2));
+
+        let mut map = HashMap::new();
+
+        map.insert(keys1, "enter");
+
+        assert_eq!(map.get(&keys2), Some(&"enter"));
+        assert_eq!(map.get(&Keymap::new("a<cr>").unwrap()), None);
+    }
+
     #[test]
     fn invalid_keymap() {
         let keymap = Keymap::new("<leader");
@@ -495,6 +3734,52 @@ mod tests {
         assert!(keymap.is_err());
     }
 
+    #[test]
+    fn retain_keys() {
+        let keys = Keymap::new("<leader>a1<c-b>;<cr>C").unwrap();
+
+        assert_eq!(
+            keys.retain_keys(|k| k.is_alpha()),
+            Keymap::new("a<c-b>C").unwrap()
+        );
+        assert_eq!(
+            keys.retain_keys(|k| k.is_alpha()).to_string(),
+            "a<c-b>C".to_string()
+        );
+        assert!(keys.retain_keys(|_| false).as_vec().is_empty());
+    }
+
+    #[test]
+    fn build_keymap() {
+        let mut keys1 = Keymap::new("").unwrap();
+
+        keys1.push(Key::new("<leader>").unwrap());
+        keys1.push(Key::new("w").unwrap());
+
+        let mut keys2 = Keymap::new("<leader>").unwrap();
+
+        keys2.extend(Keymap::new("wq").unwrap());
+
+        let keys3 = ["<c-b>", "j", "J"]
+            .into_iter()
+            .map(|k| Key::new(k).unwrap())
+            .collect::<Keymap>();
+
+        assert_eq!(keys1, Keymap::new("<leader>w").unwrap());
+        assert_eq!(keys2, Keymap::new("<leader>wq").unwrap());
+        assert_eq!(keys3, Keymap::new("<c-b>jJ").unwrap());
+    }
+
+    #[test]
+    fn key_consts() {
+        assert_eq!(Key::ENTER, Key::new("<CR>").unwrap());
+        assert_eq!(Key::ESC, Key::new("<Esc>").unwrap());
+        assert_eq!(Key::TAB, Key::new("<Tab>").unwrap());
+        assert_eq!(Key::SPACE, Key::new("<Space>").unwrap());
+        assert_eq!(Key::BACKSPACE, Key::new("<BS>").unwrap());
+        assert_eq!(Key::DELETE, Key::new("<Del>").unwrap());
+    }
+
     #[test]
     fn display_key() {
         let key1 = Key::new("A").unwrap();
@@ -510,6 +3795,24 @@ mod tests {
         assert_eq!(key4.to_string(), "<SPACE>".to_string());
         assert_eq!(key5.to_string(), "<DEL>".to_string());
         assert_eq!(key6.to_string(), "<CR>".to_string());
+
+        let cases = [
+            ("<s-a>", "A"),
+            ("a", "a"),
+            ("<c-a>", "<c-a>"),
+            ("<C-S-A>", "<c-s-a>"),
+            ("<s-a-c-b>", "<c-a-s-b>"),
+            ("<a-CR>", "<a-CR>"),
+            ("<s-1>", "<s-1>"),
+            ("<c-lt>", "<c-LT>"),
+            ("<c->>", "<c->>"),
+            ("<F5>", "<F5>"),
+            ("<s-space>", "<s-SPACE>"),
+        ];
+
+        for (tag, expected) in cases {
+            assert_eq!(Key::new(tag).unwrap().to_string(), expected);
+        }
     }
 
     #[test]
@@ -546,6 +3849,261 @@ mod tests {
         assert_eq!(keys2.to_string(), "<LT>abAsB");
     }
 
+    #[test]
+    fn custom_aliases() {
+        let options = ParseOptions {
+            aliases: vec![
+                ("Return".to_string(), Key::ENTER),
+                ("Spc".to_string(), Key::SPACE),
+                ("CR".to_string(), Key::new("<NL>").unwrap()),
+            ],
+            ..Default::default()
+        };
+
+        let keys =
+            Keymap::new_with_options("<return><c-Return><Spc><cr><Enter>", &options).unwrap();
+
+        assert_eq!(keys, Keymap::new("<CR><c-CR><Space><NL><CR>").unwrap());
+        assert_eq!(
+            Key::new_with_options("<RETURN>", &options).unwrap(),
+            Key::ENTER
+        );
+        assert_eq!(
+            Key::new("<Return>").unwrap_err().kind(),
+            ErrorKind::UnknownKey
+        );
+    }
+
+    #[test]
+    fn preserve_leader() {
+        let options = ParseOptions {
+            preserve_leader: true,
+            ..Default::default()
+        };
+        let comma = ParseOptions {
+            leader: Key::new(",").unwrap(),
+            ..options.clone()
+        };
+
+        let keys1 = Keymap::new_with_options("<leader>w<Space>", &options).unwrap();
+        let keys2 = Keymap::new_with_options("<c-leader><LEADER>", &comma).unwrap();
+
+        assert_eq!(keys1.to_string(), "<leader>w<SPACE>");
+        assert_eq!(format!("{keys1:#}"), "<leader>w<space>");
+        assert_eq!(keys1, Keymap::new("<space>w<space>").unwrap());
+        assert_eq!(keys1.canonical(), "<SPACE>w<SPACE>");
+        assert_eq!(keys2.to_string(), "<c-leader><leader>");
+        assert_eq!(
+            Keymap::new_with_options(&keys2.to_string(), &comma).unwrap(),
+            keys2
+        );
+        assert_eq!(Keymap::new("<leader>w").unwrap().to_string(), "<SPACE>w");
+    }
+
+    #[test]
+    fn modified_special_keys() {
+        let cases = [
+            ("<c-space>", "<c-SPACE>", "<c-space>"),
+            ("<C-Space>", "<c-SPACE>", "<c-space>"),
+            ("<a-esc>", "<a-ESC>", "<a-esc>"),
+            ("<c-bs>", "<c-BS>", "<c-bs>"),
+            ("<c-cr>", "<c-CR>", "<c-cr>"),
+            ("<s-cr>", "<s-CR>", "<s-cr>"),
+            ("<c-tab>", "<c-TAB>", "<c-tab>"),
+            ("<c-s-tab>", "<c-BackTab>", "<c-backtab>"),
+            ("<a-del>", "<a-DEL>", "<a-del>"),
+            ("<c-lt>", "<c-LT>", "<c-lt>"),
+            ("<c-a-s-space>", "<c-a-s-SPACE>", "<c-a-s-space>"),
+        ];
+
+        for (tag, display, alternate) in cases {
+            let key = Key::new(tag).unwrap();
+
+            assert_eq!(key.to_string(), display, "{tag}");
+            assert_eq!(format!("{key:#}"), alternate, "{tag}");
+            assert_eq!(Key::new(display).unwrap(), key, "{tag}");
+            assert_eq!(Key::new(alternate).unwrap(), key, "{tag}");
+            assert_eq!(Keymap::new(display).unwrap().as_slice(), &[key], "{tag}");
+        }
+
+        let names = ["space", "cr", "tab", "esc", "bs", "del", "lt", "nl"];
+        let prefixes = ["", "c-", "a-", "s-", "c-a-", "c-s-", "a-s-", "c-a-s-"];
+
+        for name in names {
+            for prefix in prefixes {
+                let key = Key::new(format!("<{prefix}{name}>")).unwrap();
+
+                assert_eq!(Key::new(key.to_string()).unwrap(), key, "<{prefix}{name}>");
+            }
+        }
+
+        assert_ne!(Key::new("<c-space>").unwrap(), Key::SPACE);
+        assert_eq!(Key::new("<c-space>").unwrap().code(), Code::Char(' '));
+    }
+
+    #[test]
+    fn key_name() {
+        let cases = [
+            ("<cr>", "CR"),
+            ("<lt>", "LT"),
+            (">", ">"),
+            ("<c->>", "c->"),
+            ("<c-s-a>", "c-s-a"),
+            ("<a-F1>", "a-F1"),
+            ("<s-tab>", "BackTab"),
+            ("~", "~"),
+        ];
+
+        for (tag, name) in cases {
+            assert_eq!(Key::new(tag).unwrap().name(), name, "{tag}");
+        }
+    }
+
+    #[test]
+    fn display_alternate() {
+        let key1 = Key::new("<CR>").unwrap();
+        let key2 = Key::new("<c-a>").unwrap();
+        let key3 = Key::new("<a-Esc>").unwrap();
+        let keys = Keymap::new("<leader>w<c-a>A<CR>").unwrap();
+
+        assert_eq!(format!("{key1}"), "<CR>");
+        assert_eq!(format!("{key1:#}"), "<cr>");
+        assert_eq!(format!("{key2}"), "<c-a>");
+        assert_eq!(format!("{key2:#}"), "<c-a>");
+        assert_eq!(format!("{key3:#}"), "<a-esc>");
+        assert_eq!(format!("{keys}"), "<SPACE>w<c-a>A<CR>");
+        assert_eq!(format!("{keys:#}"), "<space>w<c-a>A<cr>");
+        assert_eq!(Keymap::new(format!("{keys:#}")).unwrap(), keys);
+    }
+
+    #[test]
+    fn display_byte_len() {
+        let keymaps = [
+            "",
+            "NewYonk",
+            "<lt>abAs<s-b>",
+            "<leader><cr><tab><esc><bs><del>",
+            "<a-~><c-#><s-&>",
+            "<c-a><a-CR><s-1>",
+        ];
+
+        for keymap in keymaps {
+            let keymap = Keymap::new(keymap).unwrap();
+
+            assert_eq!(keymap.display_byte_len(), keymap.to_string().len());
+        }
+    }
+
+    #[test]
+    fn describe_keymap() {
+        let keys1 = Keymap::new("<leader>wq").unwrap();
+        let keys2 = Keymap::new("<c-w>").unwrap();
+        let keys3 = Keymap::new("<a-lt>").unwrap();
+
+        assert_eq!(keys1.describe(), "Leader then W then Q");
+        assert_eq!(keys1.canonical(), "<SPACE>wq");
+        assert_eq!(
+            Keymap::new("<space>wq").unwrap().describe(),
+            "Space then W then Q"
+        );
+        assert_eq!(Key::new("<c-leader>").unwrap().describe(), "Ctrl+Leader");
+        assert_eq!(keys2.describe(), Key::new("<c-w>").unwrap().describe());
+        assert_eq!(keys2.describe(), "Ctrl+W");
+        assert_eq!(keys3.describe(), "Alt+<");
+        assert_eq!(Keymap::new("").unwrap().describe(), "");
+    }
+
+    #[test]
+    fn keymap_tokens() {
+        let keys = Keymap::new("<leader>A<c-cr><a-lt><s-1>j<LeftMouse><c-s-a><c-a-Tab>").unwrap();
+        let tokens = keys.to_tokens();
+
+        assert_eq!(tokens.len(), 9);
+        assert_eq!(tokens[0].name, "SPACE");
+        assert_eq!((tokens[1].name.as_str(), tokens[1].shift), ("a", true));
+        assert_eq!((tokens[2].name.as_str(), tokens[2].ctrl), ("CR", true));
+        assert_eq!((tokens[3].name.as_str(), tokens[3].alt), ("LT", true));
+        assert_eq!(Keymap::from_tokens(&tokens).unwrap(), keys);
+
+        let mut invalid = tokens.clone();
+
+        invalid[0].super_ = true;
+
+        assert!(Keymap::from_tokens(&invalid).is_err());
+    }
+
+    #[test]
+    fn display_round_trip() {
+        let chars = (' '..='~').filter(|c| *c != '<').map(|c| c.to_string());
+        let names = code::NAMED_KEYS.iter().map(|(n, _)| n.to_string());
+        let mut keys = vec![];
+
+        for base in chars.chain(names) {
+            for bits in 0u8..8 {
+                let prefix = [(0b010, "c-"), (0b100, "a-"), (0b001, "s-")]
+                    .iter()
+                    .filter(|(bit, _)| bits & bit != 0)
+                    .map(|(_, p)| *p)
+                    .collect::<String>();
+
+                if let Ok(key) = Key::new(format!("<{prefix}{base}>")) {
+                    assert_eq!(Key::new(key.to_string()).unwrap(), key, "{key}");
+                    assert!(!key.is_modifier_only(), "{key}");
+
+                    keys.push(key);
+                }
+            }
+        }
+
+        assert!(keys.len() > 1000);
+
+        let keymap = Keymap::from(keys);
+
+        assert_eq!(Keymap::new(keymap.to_string()).unwrap(), keymap);
+        assert_eq!(
+            Keymap::new("<c->>a<a->><->").unwrap(),
+            Keymap::from(vec![
+                Key::new("<c->>").unwrap(),
+                Key::new("a").unwrap(),
+                Key::new("<a->>").unwrap(),
+                Key::new("-").unwrap(),
+            ])
+        );
+    }
+
+    #[test]
+    fn ord_key() {
+        let mut keys = ["<c-a>", "b", "<s-a>", "a", "<cr>", "A", "<c-b>"]
+            .map(|s| Key::new(s).unwrap())
+            .to_vec();
+
+        keys.sort();
+
+        let sorted = ["<cr>", "a", "A", "A", "<c-a>", "b", "<c-b>"].map(|s| Key::new(s).unwrap());
+
+        assert_eq!(keys, sorted);
+        assert_eq!(
+            Key::new("A").unwrap().cmp(&Key::new("<s-a>").unwrap()),
+            std::cmp::Ordering::Equal
+        );
+    }
+
+    #[test]
+    fn ord_keymap() {
+        let mut keymaps = ["h", "gg", "", "<c-a>", "g", "<s-z>Z", "gd", "a"]
+            .map(|s| Keymap::new(s).unwrap())
+            .to_vec();
+
+        keymaps.sort();
+
+        let sorted =
+            ["", "a", "<c-a>", "g", "gd", "gg", "h", "ZZ"].map(|s| Keymap::new(s).unwrap());
+
+        assert_eq!(keymaps, sorted);
+        assert_eq!(keymaps.binary_search(&Keymap::new("gg").unwrap()), Ok(5));
+        assert!(Keymap::new("").unwrap() < Keymap::new("<cr>").unwrap());
+    }
+
     #[test]
     fn debug_keymap() {
         let keys1 = Keymap::new("aa").unwrap();
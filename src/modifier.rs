@@ -1,41 +1,112 @@
 #[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
-pub(crate) struct KeyModifiers(pub(crate) KeyModifier);
+/// Modifiers held with a [Key](crate::Key).
+///
+/// # Example
+///
+/// ```
+/// use viks::{Key, KeyModifiers};
+///
+/// # fn main() {
+/// let modifiers = Key::new("<c-a>").unwrap().modifiers();
+///
+/// assert!(modifiers.is_ctrl());
+/// assert!(!modifiers.is_shift());
+/// assert!(!modifiers.is_alt());
+/// assert_eq!(modifiers, KeyModifiers::CONTROL);
+/// # }
+/// ```
+///
+/// The modifiers are combined with `|`, e.g. `KeyModifiers::CONTROL | KeyModifiers::SHIFT`.
+pub struct KeyModifiers(pub(crate) u8);
 
 impl KeyModifiers {
-    pub(crate) fn is_shift(&self) -> bool {
-        self.0 & KeyModifier::Shift == KeyModifier::Shift
+    /// No modifier.
+    pub const NONE: KeyModifiers = KeyModifiers(0b0000);
+    /// Shift modifier.
+    pub const SHIFT: KeyModifiers = KeyModifiers(0b0001);
+    /// Control modifier.
+    pub const CONTROL: KeyModifiers = KeyModifiers(0b0010);
+    /// Alt modifier.
+    pub const ALT: KeyModifiers = KeyModifiers(0b0100);
+
+    pub(crate) fn from_name(name: &str) -> Option<KeyModifiers> {
+        if ["s", "shift"].iter().any(|n| name.eq_ignore_ascii_case(n)) {
+            Some(KeyModifiers::SHIFT)
+        } else if ["c", "ctrl", "control"]
+            .iter()
+            .any(|n| name.eq_ignore_ascii_case(n))
+        {
+            Some(KeyModifiers::CONTROL)
+        } else if ["a", "alt"].iter().any(|n| name.eq_ignore_ascii_case(n)) {
+            Some(KeyModifiers::ALT)
+        } else {
+            None
+        }
+    }
+
+    pub(crate) fn contains(&self, other: KeyModifiers) -> bool {
+        self.0 & other.0 == other.0
     }
 
-    pub(crate) fn is_ctrl(&self) -> bool {
-        self.0 & KeyModifier::Control == KeyModifier::Control
+    /// Returns `true` if the Shift modifier is held.
+    pub fn is_shift(&self) -> bool {
+        self.contains(KeyModifiers::SHIFT)
     }
 
-    pub(crate) fn is_alt(&self) -> bool {
-        self.0 & KeyModifier::Alt == KeyModifier::Alt
+    /// Returns `true` if the Control modifier is held.
+    pub fn is_ctrl(&self) -> bool {
+        self.contains(KeyModifiers::CONTROL)
+    }
+
+    /// Returns `true` if the Alt modifier is held.
+    pub fn is_alt(&self) -> bool {
+        self.contains(KeyModifiers::ALT)
     }
 }
 
-#[repr(u8)]
-#[derive(Clone, Copy, Hash, PartialEq, Eq, PartialOrd, Ord)]
-pub(crate) enum KeyModifier {
-    Shift = 0b0001,
-    Control = 0b0010,
-    Alt = 0b0100,
-    None = 0b0000,
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+/// Single modifier, ordered as `Display` of [Key](crate::Key) prefixes, e.g. `<c-a-s-x>`.
+pub enum Modifier {
+    /// Control modifier, `c-`.
+    Ctrl,
+    /// Alt modifier, `a-`.
+    Alt,
+    /// Shift modifier, `s-`.
+    Shift,
 }
 
-impl std::ops::BitAnd for KeyModifier {
-    type Output = KeyModifier;
+impl From<Modifier> for KeyModifiers {
+    fn from(value: Modifier) -> Self {
+        match value {
+            Modifier::Ctrl => KeyModifiers::CONTROL,
+            Modifier::Alt => KeyModifiers::ALT,
+            Modifier::Shift => KeyModifiers::SHIFT,
+        }
+    }
+}
+
+impl core::ops::BitAnd for KeyModifiers {
+    type Output = KeyModifiers;
 
     fn bitand(self, rhs: Self) -> Self::Output {
-        unsafe { std::mem::transmute(self as u8 & rhs as u8) }
+        KeyModifiers(self.0 & rhs.0)
     }
 }
 
-impl std::ops::BitOr for KeyModifier {
-    type Output = KeyModifier;
+impl core::ops::BitOr for KeyModifiers {
+    type Output = KeyModifiers;
 
     fn bitor(self, rhs: Self) -> Self::Output {
-        unsafe { std::mem::transmute(self as u8 | rhs as u8) }
+        KeyModifiers(self.0 | rhs.0)
+    }
+}
+
+impl core::fmt::Debug for KeyModifiers {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("KeyModifiers")
+            .field("shift", &self.is_shift())
+            .field("ctrl", &self.is_ctrl())
+            .field("alt", &self.is_alt())
+            .finish()
     }
 }
@@ -0,0 +1,498 @@
+//! Parser of the `*map` line in `.vimrc`.
+//!
+//! ```
+//! use viks::Keymap;
+//! use viks::noremap;
+//!
+//! # fn main() {
+//! let line = noremap::parse_line("nnoremap <silent> <leader>w :w<CR>").unwrap();
+//!
+//! assert_eq!(line.command(), "nnoremap");
+//! assert_eq!(line.modes(), &[noremap::MapMode::Normal]);
+//! assert!(!line.is_recursive());
+//! assert!(line.is_silent());
+//! assert_eq!(line.lhs(), &Keymap::new("<leader>w").unwrap());
+//! assert_eq!(line.rhs(), Some(&Keymap::new(":w<CR>").unwrap()));
+//! # }
+//! ```
+
+use crate::{Error, ErrorKind, Key, Keymap, ParseOptions};
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+/// Mode of the mapping, as `:h map-modes` of vim.
+///
+/// # Example
+///
+/// ```
+/// use viks::noremap::MapMode;
+///
+/// # fn main() {
+/// assert_eq!(MapMode::from_command("nnoremap"), Some(&[MapMode::Normal][..]));
+/// assert_eq!(
+///     MapMode::from_command("map!"),
+///     Some(&[MapMode::Insert, MapMode::CommandLine][..])
+/// );
+/// # }
+/// ```
+pub enum MapMode {
+    /// Normal mode, `nmap`.
+    Normal,
+    /// Visual mode, `xmap`. `vmap` is both Visual and Select.
+    Visual,
+    /// Select mode, `smap`.
+    Select,
+    /// Operator-pending mode, `omap`.
+    OperatorPending,
+    /// Insert mode, `imap`.
+    Insert,
+    /// Command-line mode, `cmap`.
+    CommandLine,
+    /// Language mapping of Insert, Command-line and Lang-Arg, `lmap`.
+    Lang,
+    /// Terminal mode, `tmap`.
+    Terminal,
+}
+
+impl MapMode {
+    /// Get the modes of the `*map` or `*noremap` command.
+    ///
+    /// The bare `map` is Normal, Visual, Select and Operator-pending,
+    /// and `map!` is Insert and Command-line.
+    ///
+    /// Returns `None` if the command is not `*map` or `*noremap`.
+    pub fn from_command(command: &str) -> Option<&'static [MapMode]> {
+        use MapMode::*;
+
+        let (command, bang) = match command.strip_suffix('!') {
+            Some(command) => (command, true),
+            None => (command, false),
+        };
+
+        let prefix = command
+            .strip_suffix("noremap")
+            .or_else(|| command.strip_suffix("map"))?;
+
+        let modes: &[MapMode] = match (prefix, bang) {
+            ("", false) => &[Normal, Visual, Select, OperatorPending],
+            ("", true) => &[Insert, CommandLine],
+            ("n", false) => &[Normal],
+            ("v", false) => &[Visual, Select],
+            ("x", false) => &[Visual],
+            ("s", false) => &[Select],
+            ("o", false) => &[OperatorPending],
+            ("i", false) => &[Insert],
+            ("l", false) => &[Lang],
+            ("c", false) => &[CommandLine],
+            ("t", false) => &[Terminal],
+            _ => return None,
+        };
+
+        Some(modes)
+    }
+}
+
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+/// Parsed `*map` line, e.g. `nnoremap <leader>w :w<CR>`.
+pub struct NoremapLine {
+    command: String,
+    modes: &'static [MapMode],
+    recursive: bool,
+    buffer: bool,
+    silent: bool,
+    unique: bool,
+    expr: bool,
+    lhs: Keymap,
+    rhs: Option<Keymap>,
+    rhs_raw: String,
+}
+
+impl NoremapLine {
+    /// Get the command, e.g. `nnoremap` or `map!`.
+    pub fn command(&self) -> &str {
+        &self.command
+    }
+
+    /// Get the modes of the command.
+    pub fn modes(&self) -> &[MapMode] {
+        self.modes
+    }
+
+    /// Returns `true` if the command is `*map`, not `*noremap`.
+    pub fn is_recursive(&self) -> bool {
+        self.recursive
+    }
+
+    /// Returns `true` if `<buffer>` is given.
+    pub fn is_buffer(&self) -> bool {
+        self.buffer
+    }
+
+    /// Returns `true` if `<silent>` is given.
+    pub fn is_silent(&self) -> bool {
+        self.silent
+    }
+
+    /// Returns `true` if `<unique>` is given.
+    pub fn is_unique(&self) -> bool {
+        self.unique
+    }
+
+    /// Returns `true` if `<expr>` is given, the rhs is the expression and [NoremapLine::rhs] is `None`.
+    pub fn is_expr(&self) -> bool {
+        self.expr
+    }
+
+    /// Get the keys to map.
+    pub fn lhs(&self) -> &Keymap {
+        &self.lhs
+    }
+
+    /// Get the keys mapped to, the literal whitespace is its key, e.g. `<Space>` and `<Tab>`.
+    ///
+    /// The rhs is parsed with [ParseOptions::strict] disabled, so the tag that is not the key,
+    /// e.g. `<Cmd>` or `<SID>`, is the [Code::Unknown](crate::Code::Unknown) key.
+    ///
+    /// Returns `None` if the rhs is the expression of `<expr>`, or is not the keys, e.g. `:echo 1 < 2<CR>`,
+    /// use [NoremapLine::rhs_raw] for them.
+    pub fn rhs(&self) -> Option<&Keymap> {
+        self.rhs.as_ref()
+    }
+
+    /// Get the raw string mapped to, e.g. `:echo "hi"<CR>`, this is always available.
+    pub fn rhs_raw(&self) -> &str {
+        &self.rhs_raw
+    }
+}
+
+/// Parse the `*map` line, e.g. `nnoremap <leader>w :w<CR>`.
+///
+/// The leading whitespaces and `:` are ignored.
+///
+/// The arguments `<buffer>`, `<silent>`, `<unique>` and `<expr>` are accepted
+/// in any order between the command and the lhs.
+/// With `<expr>`, the rhs is the expression and is not parsed as the keys, see [NoremapLine::rhs].
+///
+/// # Example
+///
+/// ```
+/// use viks::Keymap;
+/// use viks::noremap;
+///
+/// # fn main() {
+/// let line = noremap::parse_line("imap <buffer> jk <Esc>").unwrap();
+///
+/// assert!(line.is_recursive());
+/// assert!(line.is_buffer());
+/// assert_eq!(line.lhs(), &Keymap::new("jk").unwrap());
+/// # }
+/// ```
+///
+/// # Error
+///
+/// Returns an error if the command is not `*map`, the lhs or rhs is missing,
+/// the argument is after the lhs, or the lhs is not available as [Keymap].
+/// The rhs that is not available as [Keymap] is not an error, see [NoremapLine::rhs].
+/// The error has the byte offset in the line.
+pub fn parse_line(line: &str) -> crate::Result<NoremapLine> {
+    let invalid = |offset: usize| Error::new(ErrorKind::InvalidFormat, line).with_offset(offset);
+
+    let mut rest = line.trim_start_matches(|c: char| c.is_ascii_whitespace() || c == ':');
+
+    let (command, after) = rest
+        .split_once(|c: char| c.is_ascii_whitespace())
+        .unwrap_or((rest, ""));
+
+    let Some(modes) = MapMode::from_command(command) else {
+        return Err(invalid(offset_in(line, command)));
+    };
+
+    rest = after.trim_start();
+
+    let mut buffer = false;
+    let mut silent = false;
+    let mut unique = false;
+    let mut expr = false;
+
+    let lhs = loop {
+        let (token, after) = rest
+            .split_once(|c: char| c.is_ascii_whitespace())
+            .unwrap_or((rest, ""));
+
+        rest = after.trim_start();
+
+        match token {
+            "" => return Err(invalid(line.len())),
+            "<buffer>" => buffer = true,
+            "<silent>" => silent = true,
+            "<unique>" => unique = true,
+            "<expr>" => expr = true,
+            lhs => break lhs,
+        }
+    };
+
+    if rest.is_empty() {
+        return Err(invalid(line.len()));
+    }
+
+    let rhs_first = rest.split(|c: char| c.is_ascii_whitespace()).next();
+
+    if rhs_first.is_some_and(|token| ARGUMENTS.contains(&token)) {
+        return Err(invalid(offset_in(line, rest)));
+    }
+
+    let lhs_offset = offset_in(line, lhs);
+    let lhs = Keymap::new(lhs).map_err(|e| shift_offset(e, lhs_offset))?;
+    // the rhs of `<expr>` is the expression, not the keys
+    let rhs = match expr {
+        true => None,
+        false => parse_rhs(rest),
+    };
+
+    Ok(NoremapLine {
+        command: command.to_string(),
+        modes,
+        recursive: !command.contains("noremap"),
+        buffer,
+        silent,
+        unique,
+        expr,
+        lhs,
+        rhs,
+        rhs_raw: rest.to_string(),
+    })
+}
+
+const ARGUMENTS: [&str; 4] = ["<buffer>", "<silent>", "<unique>", "<expr>"];
+
+/// Parse the rhs leniently, the literal ascii whitespace is the key of its byte, e.g. `<Space>` and `<Tab>`.
+fn parse_rhs(rhs: &str) -> Option<Keymap> {
+    let options = ParseOptions {
+        strict: false,
+        ..Default::default()
+    };
+
+    let mut keys = Vec::new();
+    let mut start = 0;
+
+    for (i, c) in rhs.char_indices().filter(|(_, c)| c.is_ascii_whitespace()) {
+        keys.extend(Keymap::new_with_options(&rhs[start..i], &options).ok()?);
+        keys.push(Key::from_byte(c as u8).ok()?);
+        start = i + 1;
+    }
+
+    keys.extend(Keymap::new_with_options(&rhs[start..], &options).ok()?);
+
+    Some(Keymap(keys))
+}
+
+/// Get the byte offset of the `part` sliced from the `line`.
+fn offset_in(line: &str, part: &str) -> usize {
+    part.as_ptr() as usize - line.as_ptr() as usize
+}
+
+fn shift_offset(e: Error, by: usize) -> Error {
+    match e.offset() {
+        Some(offset) => e.with_offset(offset + by),
+        None => e,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::noremap::*;
+    use crate::*;
+
+    #[test]
+    fn parse_lines() {
+        let line1 = parse_line("nnoremap <leader>w :w<CR>").unwrap();
+        let line2 = parse_line("  inoremap jk <Esc>").unwrap();
+        let line3 = parse_line(":nmap <buffer> <silent> <c-j> <c-w>j").unwrap();
+        let line4 = parse_line("noremap! <c-a> <Home>").unwrap();
+        let line5 = parse_line("nnoremap <leader>e :echo \"hi\"<CR>").unwrap();
+
+        assert_eq!(line1.command(), "nnoremap");
+        assert!(!line1.is_recursive());
+        assert_eq!(line1.lhs(), &Keymap::new("<space>w").unwrap());
+        assert_eq!(line1.rhs(), Some(&Keymap::new(":w<cr>").unwrap()));
+        assert_eq!(line1.rhs_raw(), ":w<CR>");
+
+        assert_eq!(line2.command(), "inoremap");
+        assert_eq!(line2.lhs(), &Keymap::new("jk").unwrap());
+        assert_eq!(line2.rhs(), Some(&Keymap::new("<esc>").unwrap()));
+
+        assert_eq!(line3.command(), "nmap");
+        assert!(line3.is_recursive());
+        assert!(line3.is_buffer() && line3.is_silent());
+        assert_eq!(line3.lhs(), &Keymap::new("<c-j>").unwrap());
+        assert_eq!(line3.rhs(), Some(&Keymap::new("<c-w>j").unwrap()));
+
+        assert_eq!(line4.command(), "noremap!");
+        assert!(!line4.is_buffer() && !line4.is_silent());
+
+        assert_eq!(
+            line5.rhs(),
+            Some(&Keymap::new(":echo<space>\"hi\"<cr>").unwrap())
+        );
+        assert_eq!(line5.rhs_raw(), ":echo \"hi\"<CR>");
+    }
+
+    #[test]
+    fn map_arguments() {
+        let line1 = parse_line("nnoremap <silent> <unique> <leader>x :close<CR>").unwrap();
+        let line2 = parse_line("inoremap <expr> <buffer> <Tab> pumvisible()").unwrap();
+        let err1 = parse_line("nnoremap <silent> <nowait> x y").unwrap_err();
+        let err2 = parse_line("nnoremap x <silent> y").unwrap_err();
+
+        assert!(line1.is_silent() && line1.is_unique());
+        assert!(!line1.is_buffer() && !line1.is_expr());
+        assert_eq!(line1.lhs(), &Keymap::new("<space>x").unwrap());
+        assert_eq!(line1.rhs(), Some(&Keymap::new(":close<cr>").unwrap()));
+
+        assert!(line2.is_expr() && line2.is_buffer());
+        assert!(!line2.is_silent() && !line2.is_unique());
+        assert_eq!(line2.lhs(), &Keymap::new("<tab>").unwrap());
+        assert_eq!(line2.rhs(), None);
+        assert_eq!(line2.rhs_raw(), "pumvisible()");
+
+        // the expression is not parsed as the keys even if it could be
+        let line4 = parse_line("nnoremap <expr> n v:count > 0 ? 'n' : 'N'").unwrap();
+        let line5 = parse_line("nnoremap <silent> <expr> k v:count<1 ? 'gk' : 'k'").unwrap();
+
+        assert!(line4.is_expr() && !line4.is_silent());
+        assert_eq!(line4.lhs(), &Keymap::new("n").unwrap());
+        assert_eq!(line4.rhs(), None);
+        assert_eq!(line4.rhs_raw(), "v:count > 0 ? 'n' : 'N'");
+        assert!(line5.is_expr() && line5.is_silent());
+        assert_eq!(line5.rhs(), None);
+        assert_eq!(line5.rhs_raw(), "v:count<1 ? 'gk' : 'k'");
+        assert!(
+            parse_line("nnoremap n v:count > 0 ? 'n' : 'N'")
+                .unwrap()
+                .rhs()
+                .is_some()
+        );
+
+        assert_eq!(err1.kind(), ErrorKind::UnknownKey);
+        assert_eq!(err1.offset(), Some(18));
+        assert_eq!(err2.kind(), ErrorKind::InvalidFormat);
+        assert_eq!(err2.offset(), Some(11));
+        assert!(parse_line("nnoremap x <expr>").is_err());
+
+        let line3 = parse_line("nnoremap <silent>\tx\t:echo\t\"hi\"<CR>").unwrap();
+
+        assert!(line3.is_silent());
+        assert_eq!(line3.lhs(), &Keymap::new("x").unwrap());
+        assert_eq!(
+            line3.rhs(),
+            Some(&Keymap::new(":echo<tab>\"hi\"<cr>").unwrap())
+        );
+        assert_eq!(parse_line("nnoremap x\t:echo\t<c-\t").unwrap().rhs(), None);
+    }
+
+    #[test]
+    fn lenient_rhs() {
+        let options = ParseOptions {
+            strict: false,
+            ..Default::default()
+        };
+
+        let line1 = parse_line("nnoremap <leader>f <Cmd>Telescope<CR>").unwrap();
+        let line2 = parse_line("nnoremap x :call <SID>Foo()<CR>").unwrap();
+        let line3 = parse_line("nnoremap <expr> j v:count < 1 ? 'gj' : 'j'").unwrap();
+        let line4 =
+            parse_line("inoremap <expr> <Tab> pumvisible() ? \"\\<C-n>\" : \"\\<Tab>\"").unwrap();
+        let line5 = parse_line("nnoremap x :echo 1 < 2<CR>").unwrap();
+
+        assert_eq!(
+            line1.rhs(),
+            Some(&Keymap::new_with_options("<Cmd>Telescope<CR>", &options).unwrap())
+        );
+        assert_eq!(line1.rhs().unwrap()[0].code(), Code::Unknown("Cmd"));
+        assert_eq!(line2.rhs().unwrap()[6].code(), Code::Unknown("SID"));
+        assert_eq!(line2.rhs_raw(), ":call <SID>Foo()<CR>");
+
+        assert!(line3.is_expr());
+        assert_eq!(line3.lhs(), &Keymap::new("j").unwrap());
+        assert_eq!(line3.rhs(), None);
+        assert_eq!(line3.rhs_raw(), "v:count < 1 ? 'gj' : 'j'");
+        assert_eq!(line4.rhs(), None);
+        assert_eq!(line4.rhs_raw(), "pumvisible() ? \"\\<C-n>\" : \"\\<Tab>\"");
+
+        assert!(!line5.is_expr());
+        assert_eq!(line5.rhs(), None);
+        assert_eq!(line5.rhs_raw(), ":echo 1 < 2<CR>");
+    }
+
+    #[test]
+    fn borrow_rhs() {
+        let line = parse_line("nnoremap <leader>w :w<CR>").unwrap();
+        let rhs: &[Key] = line.rhs().unwrap().as_slice();
+
+        assert_eq!(rhs.len(), 3);
+        assert_eq!(rhs.last(), Some(&Key::ENTER));
+
+        let mut count = 0;
+
+        for key in line.rhs().unwrap() {
+            assert!(line.rhs().unwrap().contains(key));
+
+            count += 1;
+        }
+
+        assert_eq!(count, 3);
+        assert_eq!(line.lhs().as_slice().first(), Some(&Key::SPACE));
+    }
+
+    #[test]
+    fn map_modes() {
+        use MapMode::*;
+
+        let cases: [(&str, &[MapMode]); 13] = [
+            ("nnoremap", &[Normal]),
+            ("inoremap", &[Insert]),
+            ("vnoremap", &[Visual, Select]),
+            ("xnoremap", &[Visual]),
+            ("snoremap", &[Select]),
+            ("onoremap", &[OperatorPending]),
+            ("cnoremap", &[CommandLine]),
+            ("tnoremap", &[Terminal]),
+            ("lnoremap", &[Lang]),
+            ("noremap", &[Normal, Visual, Select, OperatorPending]),
+            ("map", &[Normal, Visual, Select, OperatorPending]),
+            ("noremap!", &[Insert, CommandLine]),
+            ("nmap", &[Normal]),
+        ];
+
+        for (command, modes) in cases {
+            assert_eq!(MapMode::from_command(command), Some(modes), "{command}");
+        }
+
+        assert_eq!(MapMode::from_command("nnoremap!"), None);
+        assert_eq!(MapMode::from_command("qnoremap"), None);
+        assert_eq!(MapMode::from_command("remap"), None);
+        assert_eq!(MapMode::from_command(""), None);
+        assert_eq!(
+            parse_line("vmap <c-c> y").unwrap().modes(),
+            &[Visual, Select]
+        );
+    }
+
+    #[test]
+    fn invalid_lines() {
+        let err1 = parse_line("nnoremapp a b").unwrap_err();
+        let err2 = parse_line("nnoremap <leader>w").unwrap_err();
+        let err3 = parse_line("nnoremap <boo> b").unwrap_err();
+
+        assert_eq!(err1.kind(), ErrorKind::InvalidFormat);
+        assert_eq!(err1.offset(), Some(0));
+        assert_eq!(err2.kind(), ErrorKind::InvalidFormat);
+        assert_eq!(err3.kind(), ErrorKind::UnknownKey);
+        assert_eq!(err3.offset(), Some(9));
+        assert!(parse_line("").is_err());
+        assert!(parse_line("nnoremap <silent>").is_err());
+        assert!(parse_line("nnoremap! a b").is_err());
+        assert!(parse_line("set number").is_err());
+    }
+}
@@ -0,0 +1,94 @@
+use crate::Key;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+#[derive(Clone, Debug)]
+/// Options for [Keymap::new_with_options](crate::Keymap::new_with_options)
+/// and [Key::new_with_options].
+///
+/// # Example
+///
+/// ```
+/// use viks::{Key, Keymap, ParseOptions};
+///
+/// # fn main() {
+/// let options = ParseOptions {
+///     literal_unknown_tags: true,
+///     strict: true,
+///     leader: Key::new(",").unwrap(),
+///     unicode: false,
+///     backslash_escape: false,
+///     preserve_leader: false,
+///     aliases: vec![("Return".to_string(), Key::ENTER)],
+/// };
+///
+/// let keymap = Keymap::new_with_options("<abc><leader><Return>", &options).unwrap();
+///
+/// assert_eq!(keymap, Keymap::new("abc,<CR>").unwrap());
+/// # }
+/// ```
+pub struct ParseOptions {
+    /// Expand the unrecognized `<...>` tag to the keys of its inner chars.
+    ///
+    /// Default is `false`, the unrecognized tag is an error.
+    pub literal_unknown_tags: bool,
+
+    /// Reject the unrecognized `<...>` tag, e.g. `<BOO>`.
+    ///
+    /// If `false`, the tag is the [Code::Unknown](crate::Code::Unknown) key, and `Display` writes it verbatim.
+    /// The unknown keys are equal if the tag names are the same, e.g. `<c-BOO>` is not `<c-FOO>`.
+    /// The tag name is kept for the rest of the program, as many as the distinct names.
+    /// The tag is not expanded by [literal_unknown_tags](ParseOptions::literal_unknown_tags),
+    /// and the invalid modifier, e.g. `<x-a>`, is still an error.
+    ///
+    /// Default is `true`, the unrecognized tag is an error.
+    pub strict: bool,
+
+    /// The key that `<leader>` expands to.
+    ///
+    /// Default is `<space>`.
+    pub leader: Key,
+
+    /// Accept the non-ascii char as the key, e.g. `é` or `<c-あ>`.
+    ///
+    /// The char is kept as is, the uppercase non-ascii char doesn't have Shift.
+    ///
+    /// Default is `false`, the non-ascii char is an error.
+    pub unicode: bool,
+
+    /// Escape the key with the backslash, `\<` is `<lt>` and `\\` is `\`.
+    ///
+    /// The backslash in `<...>` is not the escape, e.g. `<c-\>`.
+    ///
+    /// Default is `false`, the backslash is the key of itself.
+    pub backslash_escape: bool,
+
+    /// Keep the `<leader>` for the `Display`, e.g. `<leader>w` is displayed as `<leader>w`.
+    ///
+    /// The key is still the same as the [leader](ParseOptions::leader).
+    ///
+    /// Default is `false`, the `<leader>` is displayed as the leader, e.g. `<SPACE>w`.
+    pub preserve_leader: bool,
+
+    /// Additional key names, e.g. `("Return", Key::ENTER)` for `<Return>`.
+    ///
+    /// The name is case-insensitive, and is looked up before the built-in names and `<leader>`.
+    /// The modifiers are added to the key, e.g. `<c-Return>` is `<c-CR>`.
+    ///
+    /// Default is empty, only the built-in names are available.
+    pub aliases: Vec<(String, Key)>,
+}
+
+impl Default for ParseOptions {
+    fn default() -> Self {
+        ParseOptions {
+            literal_unknown_tags: false,
+            strict: true,
+            leader: Key::SPACE,
+            unicode: false,
+            backslash_escape: false,
+            preserve_leader: false,
+            aliases: Vec::new(),
+        }
+    }
+}
@@ -0,0 +1,183 @@
+use crate::{Error, ErrorKind, Key, ParseOptions};
+use alloc::string::String;
+
+#[derive(Clone, PartialEq, Eq, Hash, Debug, Default)]
+/// Incremental parser of [Keymap](crate::Keymap) syntax, fed one char at a time.
+///
+/// The key is emitted as soon as it is resolved, and the chars in `<...>` are buffered.
+///
+/// # Example
+///
+/// ```
+/// use viks::{Key, KeymapParser};
+///
+/// # fn main() {
+/// let mut parser = KeymapParser::new();
+///
+/// assert_eq!(parser.feed('<').unwrap(), None);
+/// assert_eq!(parser.feed('c').unwrap(), None);
+/// assert_eq!(parser.feed('r').unwrap(), None);
+/// assert_eq!(parser.feed('>').unwrap(), Some(Key::new("<cr>").unwrap()));
+/// assert_eq!(parser.feed('j').unwrap(), Some(Key::new("j").unwrap()));
+/// assert!(parser.finish().is_ok());
+/// # }
+/// ```
+pub struct KeymapParser {
+    in_tag: bool,
+    tag_start: usize,
+    offset: usize,
+    buf: String,
+}
+
+impl KeymapParser {
+    /// Create new KeymapParser.
+    pub fn new() -> Self {
+        KeymapParser::default()
+    }
+
+    /// Feed the char, and get the key if it is resolved.
+    ///
+    /// # Error
+    ///
+    /// Returns an error if the char or the closed tag is not available as [Key].
+    /// The error has the byte offset of the failed key or the `<` from the first fed char.
+    pub fn feed(&mut self, c: char) -> crate::Result<Option<Key>> {
+        let offset = self.offset;
+
+        self.offset += c.len_utf8();
+
+        if !self.in_tag {
+            if c == '<' {
+                self.in_tag = true;
+                self.tag_start = offset;
+                self.buf.push(c);
+
+                return Ok(None);
+            }
+
+            return Key::from_char(c, &ParseOptions::default())
+                .map(Some)
+                .map_err(|e| e.with_offset(offset));
+        }
+
+        // `>` after the modifier is the key itself, e.g. `<c->>`
+        let is_modded_gt = crate::is_modifier_prefix(&self.buf);
+
+        if c == '<' && !is_modded_gt {
+            return Err(Error::new(ErrorKind::NestedTag, &self.buf).with_offset(offset));
+        }
+
+        self.buf.push(c);
+
+        if c == '>' && !is_modded_gt {
+            let key = Key::new(&self.buf).map_err(|e| e.with_offset(self.tag_start));
+
+            self.in_tag = false;
+            self.buf.clear();
+
+            return key.map(Some);
+        }
+
+        Ok(None)
+    }
+
+    /// Returns `true` if the chars in `<...>` are buffered.
+    pub fn is_pending(&self) -> bool {
+        self.in_tag
+    }
+
+    /// Finish the feeding, and reset the parser.
+    ///
+    /// # Error
+    ///
+    /// Returns an error if the tag is not closed, or the tag is only modifiers, e.g. `<c->`.
+    pub fn finish(&mut self) -> crate::Result<()> {
+        let result = if self.in_tag && crate::is_modifier_only_tag(&self.buf) {
+            Err(Error::new(ErrorKind::MissingKey, &self.buf).with_offset(self.tag_start))
+        } else if self.in_tag {
+            Err(Error::new(ErrorKind::UnclosedTag, &self.buf).with_offset(self.tag_start))
+        } else {
+            Ok(())
+        };
+
+        *self = KeymapParser::default();
+
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    fn feed_all(parser: &mut KeymapParser, s: &str) -> Vec<Option<Key>> {
+        s.chars().map(|c| parser.feed(c).unwrap()).collect()
+    }
+
+    #[test]
+    fn feed_chars() {
+        let mut parser = KeymapParser::new();
+
+        assert_eq!(
+            feed_all(&mut parser, "<c-b>jj"),
+            vec![
+                None,
+                None,
+                None,
+                None,
+                Some(Key::new("<c-b>").unwrap()),
+                Some(Key::new("j").unwrap()),
+                Some(Key::new("j").unwrap()),
+            ]
+        );
+        assert!(!parser.is_pending());
+        assert!(parser.finish().is_ok());
+    }
+
+    #[test]
+    fn feed_same_as_keymap() {
+        let s = "<c->>a<a->><->ZZ<leader>w<lt><s-->";
+        let mut parser = KeymapParser::new();
+        let keys = feed_all(&mut parser, s)
+            .into_iter()
+            .flatten()
+            .collect::<Keymap>();
+
+        assert_eq!(keys, Keymap::new(s).unwrap());
+    }
+
+    #[test]
+    fn feed_errors() {
+        let mut parser = KeymapParser::new();
+
+        feed_all(&mut parser, "ab<c-");
+
+        assert!(parser.is_pending());
+
+        let err = parser.finish().unwrap_err();
+
+        assert_eq!(err.kind(), ErrorKind::UnclosedTag);
+        assert_eq!(err.offset(), Some(2));
+        assert!(!parser.is_pending());
+
+        feed_all(&mut parser, "<c-s->");
+
+        assert_eq!(parser.finish().unwrap_err().kind(), ErrorKind::MissingKey);
+
+        feed_all(&mut parser, "j");
+
+        assert_eq!(parser.feed('é').unwrap_err().offset(), Some(1));
+
+        feed_all(&mut parser, "<boo");
+
+        assert_eq!(parser.feed('>').unwrap_err().offset(), Some(3));
+        assert_eq!(parser.feed('k').unwrap(), Some(Key::new("k").unwrap()));
+
+        feed_all(&mut parser, "<c");
+
+        let err = parser.feed('<').unwrap_err();
+
+        assert_eq!(err.kind(), ErrorKind::NestedTag);
+        assert_eq!(err.offset(), Some(11));
+    }
+}
@@ -0,0 +1,208 @@
+use crate::{Code, Error, Keymap, ParseOptions};
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::ops::Range;
+
+#[derive(Clone, Debug)]
+/// [Keymap] with the original spelling, e.g. `<Enter>` is not rewritten to `<CR>`.
+///
+/// `Display` writes the original string, and `Eq` and `Hash` compare the parsed [Keymap]
+/// and the [unknown tags](RawKeymap::unknown_tags).
+///
+/// # Example
+///
+/// ```
+/// use viks::{Keymap, RawKeymap};
+///
+/// # fn main() {
+/// let raw = RawKeymap::new("<Enter><C-w>j").unwrap();
+///
+/// assert_eq!(raw.to_string(), "<Enter><C-w>j");
+/// assert_eq!(raw.keymap().to_string(), "<CR><c-w>j");
+/// assert_eq!(raw, Keymap::new("<cr><c-w>j").unwrap());
+/// # }
+/// ```
+pub struct RawKeymap {
+    raw: String,
+    keymap: Keymap,
+    // the index of the unknown key and the range of its tag in `raw`
+    unknown: Vec<(usize, Range<usize>)>,
+}
+
+impl RawKeymap {
+    /// Create new RawKeymap.
+    ///
+    /// # Error
+    ///
+    /// Returns an error if the string is not available as [Keymap].
+    pub fn new(s: &str) -> crate::Result<Self> {
+        RawKeymap::new_with_options(s, &ParseOptions::default())
+    }
+
+    /// Create new RawKeymap with the [ParseOptions].
+    ///
+    /// # Error
+    ///
+    /// Returns an error if the string is not available as [Keymap].
+    pub fn new_with_options(s: &str, options: &ParseOptions) -> crate::Result<Self> {
+        let mut keys = Vec::new();
+        let mut unknown = Vec::new();
+
+        Keymap::parse_each(s, options, |key, range| {
+            if matches!(key.code(), Code::Unknown(_)) {
+                unknown.push((keys.len(), range));
+            }
+
+            keys.push(key);
+        })?;
+
+        Ok(RawKeymap {
+            raw: s.to_string(),
+            keymap: Keymap::from(keys),
+            unknown,
+        })
+    }
+
+    /// Get the original string.
+    pub fn as_str(&self) -> &str {
+        &self.raw
+    }
+
+    /// Get the parsed keymap.
+    pub fn keymap(&self) -> &Keymap {
+        &self.keymap
+    }
+
+    /// Get the index and the original tag of each unknown key, see [ParseOptions::strict].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use viks::{Code, ParseOptions, RawKeymap};
+    ///
+    /// # fn main() {
+    /// let options = ParseOptions {
+    ///     strict: false,
+    ///     ..Default::default()
+    /// };
+    ///
+    /// let raw = RawKeymap::new_with_options("j<c-BOO><CR>", &options).unwrap();
+    ///
+    /// assert_eq!(raw.keymap()[1].code(), Code::Unknown("BOO"));
+    /// assert_eq!(raw.unknown_tags(), vec![(1, "<c-BOO>")]);
+    /// assert_eq!(raw.to_string(), "j<c-BOO><CR>");
+    /// # }
+    /// ```
+    pub fn unknown_tags(&self) -> Vec<(usize, &str)> {
+        self.unknown
+            .iter()
+            .map(|(index, range)| (*index, &self.raw[range.clone()]))
+            .collect()
+    }
+
+    /// Discard the original string and get the parsed keymap.
+    pub fn into_keymap(self) -> Keymap {
+        self.keymap
+    }
+}
+
+impl core::fmt::Display for RawKeymap {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str(&self.raw)
+    }
+}
+
+impl core::str::FromStr for RawKeymap {
+    type Err = Error;
+
+    fn from_str(s: &str) -> core::result::Result<Self, Self::Err> {
+        RawKeymap::new(s)
+    }
+}
+
+impl PartialEq for RawKeymap {
+    fn eq(&self, other: &Self) -> bool {
+        self.keymap == other.keymap && self.unknown_tags() == other.unknown_tags()
+    }
+}
+
+impl Eq for RawKeymap {}
+
+impl core::hash::Hash for RawKeymap {
+    fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+        self.keymap.hash(state);
+        self.unknown_tags().hash(state);
+    }
+}
+
+impl PartialEq<Keymap> for RawKeymap {
+    fn eq(&self, other: &Keymap) -> bool {
+        &self.keymap == other
+    }
+}
+
+impl PartialEq<RawKeymap> for Keymap {
+    fn eq(&self, other: &RawKeymap) -> bool {
+        self == &other.keymap
+    }
+}
+
+impl From<RawKeymap> for Keymap {
+    fn from(value: RawKeymap) -> Self {
+        value.keymap
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    #[test]
+    fn raw_spelling() {
+        let raw1 = RawKeymap::new("<Enter>").unwrap();
+        let raw2 = RawKeymap::new("<CR>").unwrap();
+        let raw3 = "<leader>w<S-A>".parse::<RawKeymap>().unwrap();
+
+        assert_eq!(raw1.to_string(), "<Enter>");
+        assert_eq!(raw1.as_str(), "<Enter>");
+        assert_eq!(raw1, raw2);
+        assert_eq!(raw1, Keymap::new("<CR>").unwrap());
+        assert_eq!(Keymap::new("<cr>").unwrap(), raw1);
+        assert_eq!(raw1.keymap().to_string(), "<CR>");
+        assert_eq!(raw3.to_string(), "<leader>w<S-A>");
+        assert_eq!(raw3.into_keymap(), Keymap::new("<space>wA").unwrap());
+        assert_eq!(
+            Keymap::from(raw2.clone()),
+            Keymap::new(raw2.to_string()).unwrap()
+        );
+        assert!(RawKeymap::new("<Enter").is_err());
+    }
+
+    #[test]
+    fn lenient_unknown_tags() {
+        let options = ParseOptions {
+            strict: false,
+            ..Default::default()
+        };
+
+        let long = "<AnUnknownTagLongerThanTheKeyNames>";
+        let s = format!("j<BOO>{long}<CR>");
+
+        assert!(RawKeymap::new(&s).is_err());
+
+        let raw = RawKeymap::new_with_options(&s, &options).unwrap();
+
+        assert_eq!(raw.keymap().len(), 4);
+        assert_eq!(raw.unknown_tags(), vec![(1, "<BOO>"), (2, long)]);
+        assert_eq!(raw.to_string(), s);
+        assert_eq!(
+            RawKeymap::new_with_options(&raw.to_string(), &options).unwrap(),
+            raw
+        );
+        assert_ne!(
+            RawKeymap::new_with_options("<FOO>", &options).unwrap(),
+            RawKeymap::new_with_options("<BOO>", &options).unwrap()
+        );
+        assert!(RawKeymap::new("<CR>").unwrap().unknown_tags().is_empty());
+    }
+}
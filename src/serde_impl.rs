@@ -4,14 +4,113 @@
 //! ```sh
 //! viks = { version = "*", features = ["serde"] }
 //! ```
+//!
+//! The human-readable formats, e.g. JSON, use the string, e.g. `"<c-b>jj"`,
+//! and [Key](crate::Key) and [Keymap](crate::Keymap) are written in the alternate `Display` form,
+//! e.g. `"<cr>"` and `"<space>w<cr>"`, so the key is written the same alone and in the keymap.
+//! The binary formats, e.g. bincode, use the compact bytes:
+//! - [Key](crate::Key) is the tuple of two bytes, the key code and the modifier bits
+//! - [Keymap](crate::Keymap) is the length-prefixed bytes, the two bytes of each key
+//!
+//! The human-readable formats accept the unicode key, see [ParseOptions::unicode](crate::ParseOptions::unicode),
+//! and write the key of `<leader>` as the resolved key, see [ParseOptions::preserve_leader](crate::ParseOptions::preserve_leader),
+//! so the written string always reads back to the same key.
+//!
+//! The unicode key is not available in the binary formats,
+//! and the unknown key, see [ParseOptions::strict](crate::ParseOptions::strict),
+//! is not available in any format since the strict parse doesn't read it back.
+
+use alloc::format;
+use alloc::vec::Vec;
+
+use crate::{Key, KeyCode, KeyModifiers, Leader, ParseOptions, Payload};
+
+/// Get the options to read the human-readable form.
+fn readable_options() -> ParseOptions {
+    ParseOptions {
+        unicode: true,
+        ..Default::default()
+    }
+}
+
+/// Get the key to write in the human-readable form, the `<leader>` is written as the resolved key.
+fn to_readable(key: &Key) -> Option<Key> {
+    (key.code != KeyCode::Unknown).then_some(Key {
+        leader: Leader::No,
+        ..*key
+    })
+}
+
+/// Get the key code and the modifier bits of the binary form.
+fn to_parts(key: &Key) -> Option<[u8; 2]> {
+    (key.payload == Payload::NONE).then_some([key.code as u8, key.modifiers.0])
+}
+
+/// Get the key of the binary form.
+fn from_parts(code: u8, modifiers: u8) -> Option<Key> {
+    let code = KeyCode::from_repr(code)?;
+
+    if modifiers & !0b111 != 0 {
+        return None;
+    }
+
+    let key = Key {
+        code,
+        modifiers: KeyModifiers::NONE,
+        payload: Payload::NONE,
+        leader: Leader::No,
+    };
+
+    Some(key.with_modifiers(KeyModifiers(modifiers)))
+}
 
 impl<'de> serde::Deserialize<'de> for crate::Key {
+    /// Accepts the string, e.g. `"<c-a>"`, or the [structured] form.
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
     where
         D: serde::Deserializer<'de>,
     {
-        let s = String::deserialize(deserializer)?;
-        crate::Key::new(&s).map_err(serde::de::Error::custom)
+        use serde::de::Error;
+
+        if deserializer.is_human_readable() {
+            return deserializer.deserialize_any(KeyVisitor);
+        }
+
+        let (code, modifiers) = <(u8, u8)>::deserialize(deserializer)?;
+
+        from_parts(code, modifiers)
+            .ok_or_else(|| D::Error::custom(format_args!("invalid key bytes: {code}, {modifiers}")))
+    }
+}
+
+struct KeyVisitor;
+
+impl<'de> serde::de::Visitor<'de> for KeyVisitor {
+    type Value = crate::Key;
+
+    fn expecting(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        f.write_str("a key string or a key struct")
+    }
+
+    fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+    where
+        E: serde::de::Error,
+    {
+        crate::Key::new_with_options(v, &readable_options()).map_err(E::custom)
+    }
+
+    fn visit_map<A>(self, map: A) -> Result<Self::Value, A::Error>
+    where
+        A: serde::de::MapAccess<'de>,
+    {
+        use serde::Deserialize;
+        use serde::de::Error;
+
+        let key = structured::StructuredKey::deserialize(
+            serde::de::value::MapAccessDeserializer::new(map),
+        )?;
+
+        crate::Key::try_from(&crate::KeyToken::from(key)).map_err(A::Error::custom)
     }
 }
 
@@ -20,17 +119,118 @@ impl serde::Serialize for crate::Key {
     where
         S: serde::Serializer,
     {
-        serializer.serialize_str(&self.to_string())
+        use serde::ser::Error;
+
+        if serializer.is_human_readable() {
+            let key = to_readable(self).ok_or_else(|| {
+                S::Error::custom(format_args!("unknown key is not available: {self}"))
+            })?;
+
+            return serializer.serialize_str(&format!("{key:#}"));
+        }
+
+        let [code, modifiers] = to_parts(self).ok_or_else(|| {
+            S::Error::custom(format_args!("key is not available in binary: {self}"))
+        })?;
+
+        (code, modifiers).serialize(serializer)
     }
 }
 
 impl<'de> serde::Deserialize<'de> for crate::Keymap {
+    /// Accepts the string, e.g. `"<c-b>jj"`, or the sequence of keys, e.g. `["<c-b>", "j", "j"]`.
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
     where
         D: serde::Deserializer<'de>,
     {
-        let s = String::deserialize(deserializer)?;
-        crate::Keymap::new(&s).map_err(serde::de::Error::custom)
+        if deserializer.is_human_readable() {
+            deserializer.deserialize_any(KeymapVisitor)
+        } else {
+            deserializer.deserialize_bytes(KeymapBytesVisitor)
+        }
+    }
+}
+
+struct KeymapVisitor;
+
+impl<'de> serde::de::Visitor<'de> for KeymapVisitor {
+    type Value = crate::Keymap;
+
+    fn expecting(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        f.write_str("a keymap string or a sequence of keys")
+    }
+
+    fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+    where
+        E: serde::de::Error,
+    {
+        crate::Keymap::new_with_options(v, &readable_options()).map_err(E::custom)
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: serde::de::SeqAccess<'de>,
+    {
+        let mut keys = Vec::with_capacity(seq.size_hint().unwrap_or(0));
+
+        while let Some(key) = seq.next_element::<crate::Key>()? {
+            keys.push(key);
+        }
+
+        Ok(crate::Keymap(keys))
+    }
+}
+
+struct KeymapBytesVisitor;
+
+impl KeymapBytesVisitor {
+    fn from_bytes<E>(bytes: &[u8]) -> Result<crate::Keymap, E>
+    where
+        E: serde::de::Error,
+    {
+        if bytes.len() % 2 != 0 {
+            return Err(E::invalid_length(bytes.len(), &KeymapBytesVisitor));
+        }
+
+        bytes
+            .chunks_exact(2)
+            .map(|parts| {
+                from_parts(parts[0], parts[1]).ok_or_else(|| {
+                    E::custom(format_args!(
+                        "invalid key bytes: {}, {}",
+                        parts[0], parts[1]
+                    ))
+                })
+            })
+            .collect()
+    }
+}
+
+impl<'de> serde::de::Visitor<'de> for KeymapBytesVisitor {
+    type Value = crate::Keymap;
+
+    fn expecting(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        f.write_str("the pairs of the key code and the modifier bits")
+    }
+
+    fn visit_bytes<E>(self, v: &[u8]) -> Result<Self::Value, E>
+    where
+        E: serde::de::Error,
+    {
+        KeymapBytesVisitor::from_bytes(v)
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: serde::de::SeqAccess<'de>,
+    {
+        let mut bytes = Vec::with_capacity(seq.size_hint().unwrap_or(0));
+
+        while let Some(byte) = seq.next_element::<u8>()? {
+            bytes.push(byte);
+        }
+
+        KeymapBytesVisitor::from_bytes(&bytes)
     }
 }
 
@@ -39,6 +239,351 @@ impl serde::Serialize for crate::Keymap {
     where
         S: serde::Serializer,
     {
-        serializer.serialize_str(&self.to_string())
+        use serde::ser::Error;
+
+        if serializer.is_human_readable() {
+            let keymap = self
+                .iter()
+                .map(|key| {
+                    to_readable(key).ok_or_else(|| {
+                        S::Error::custom(format_args!("unknown key is not available: {key}"))
+                    })
+                })
+                .collect::<Result<crate::Keymap, _>>()?;
+
+            return serializer.serialize_str(&format!("{keymap:#}"));
+        }
+
+        let mut bytes = Vec::with_capacity(self.len() * 2);
+
+        for key in self {
+            let parts = to_parts(key).ok_or_else(|| {
+                S::Error::custom(format_args!("key is not available in binary: {key}"))
+            })?;
+
+            bytes.extend(parts);
+        }
+
+        serializer.serialize_bytes(&bytes)
+    }
+}
+
+/// Structured representation of [Key](crate::Key), use with `#[serde(with = "...")]`.
+///
+/// The key is serialized as `{ "code": "a", "ctrl": true, "shift": false, "alt": false }`.
+/// `code` is the same as [KeyToken::name](crate::KeyToken::name),
+/// and the missing modifiers are `false`.
+///
+/// # Example
+///
+/// ```
+/// use viks::Key;
+///
+/// #[derive(serde::Serialize, serde::Deserialize)]
+/// struct Config {
+///     #[serde(with = "viks::serde_impl::structured")]
+///     key: Key,
+/// }
+///
+/// # fn main() {
+/// let config = Config {
+///     key: Key::new("<c-a>").unwrap(),
+/// };
+/// let json = serde_json::to_string(&config).unwrap();
+///
+/// assert_eq!(json, r#"{"key":{"code":"a","ctrl":true,"shift":false,"alt":false}}"#);
+/// # }
+/// ```
+pub mod structured {
+    use alloc::string::String;
+
+    #[derive(serde::Serialize, serde::Deserialize)]
+    pub(super) struct StructuredKey {
+        code: String,
+        #[serde(default)]
+        ctrl: bool,
+        #[serde(default)]
+        shift: bool,
+        #[serde(default)]
+        alt: bool,
+    }
+
+    impl From<StructuredKey> for crate::KeyToken {
+        fn from(value: StructuredKey) -> Self {
+            crate::KeyToken {
+                name: value.code,
+                ctrl: value.ctrl,
+                shift: value.shift,
+                alt: value.alt,
+                super_: false,
+            }
+        }
+    }
+
+    /// Serialize [Key](crate::Key) as the struct.
+    ///
+    /// The unknown key is an error, same as the string form.
+    pub fn serialize<S>(key: &crate::Key, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        use serde::Serialize;
+        use serde::ser::Error;
+
+        let key = super::to_readable(key)
+            .ok_or_else(|| S::Error::custom(format_args!("unknown key is not available: {key}")))?;
+        let token = crate::KeyToken::from(key);
+
+        StructuredKey {
+            code: token.name,
+            ctrl: token.ctrl,
+            shift: token.shift,
+            alt: token.alt,
+        }
+        .serialize(serializer)
+    }
+
+    /// Deserialize [Key](crate::Key) from the struct or the string.
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<crate::Key, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        serde::Deserialize::deserialize(deserializer)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    #[derive(serde::Serialize, serde::Deserialize, PartialEq, Debug)]
+    struct Config {
+        key: Key,
+        keymap: Keymap,
+    }
+
+    #[test]
+    fn keymap_round_trip() {
+        let keymap = Keymap::new("<leader>w<cr><lt>A").unwrap();
+        let json = serde_json::to_string(&keymap).unwrap();
+
+        assert_eq!(json, "\"<space>w<cr><lt>A\"");
+        assert_eq!(serde_json::from_str::<Keymap>(&json).unwrap(), keymap);
+    }
+
+    #[test]
+    fn config_round_trip() {
+        let config = Config {
+            key: Key::new("<c-s-a>").unwrap(),
+            keymap: Keymap::new("<c-w><Up>").unwrap(),
+        };
+        let json = serde_json::to_string(&config).unwrap();
+
+        assert_eq!(json, r#"{"key":"<c-s-a>","keymap":"<c-w><up>"}"#);
+        assert_eq!(serde_json::from_str::<Config>(&json).unwrap(), config);
+        assert!(serde_json::from_str::<Keymap>("\"<leader\"").is_err());
+    }
+
+    #[test]
+    fn key_as_keymap() {
+        for tag in [
+            "a", "A", "<c-s-a>", "<CR>", "<a-Up>", "<lt>", "<F12>", "<Space>",
+        ] {
+            let key = Key::new(tag).unwrap();
+            let json = serde_json::to_string(&key).unwrap();
+
+            assert_eq!(json, serde_json::to_string(&Keymap::from(key)).unwrap());
+            assert_eq!(serde_json::from_str::<Key>(&json).unwrap(), key);
+        }
+
+        assert_eq!(
+            serde_json::to_string(&Key::new("<a-Up>").unwrap()).unwrap(),
+            "\"<a-up>\""
+        );
+    }
+
+    #[test]
+    fn options_dependent_round_trip() {
+        let unicode = ParseOptions {
+            unicode: true,
+            ..Default::default()
+        };
+        let comma_leader = ParseOptions {
+            leader: Key::new(",").unwrap(),
+            preserve_leader: true,
+            ..Default::default()
+        };
+        let lenient = ParseOptions {
+            strict: false,
+            ..Default::default()
+        };
+
+        let keymap = Keymap::new_with_options("é<c-あ>", &unicode).unwrap();
+        let json = serde_json::to_string(&keymap).unwrap();
+
+        assert_eq!(serde_json::from_str::<Keymap>(&json).unwrap(), keymap);
+        assert_eq!(
+            serde_json::from_str::<Key>(&serde_json::to_string(&keymap[1]).unwrap()).unwrap(),
+            keymap[1]
+        );
+
+        let keymap = Keymap::new_with_options("<leader>w", &comma_leader).unwrap();
+        let json = serde_json::to_string(&keymap).unwrap();
+
+        assert_eq!(keymap.to_string(), "<leader>w");
+        assert_eq!(json, "\",w\"");
+        assert_eq!(serde_json::from_str::<Keymap>(&json).unwrap(), keymap);
+        assert_eq!(serde_json::to_string(&keymap[0]).unwrap(), "\",\"");
+
+        let keymap = Keymap::new_with_options("j<BOO>", &lenient).unwrap();
+        let err = serde_json::to_string(&keymap).unwrap_err();
+
+        assert!(err.to_string().contains("unknown key"), "{err}");
+        assert!(serde_json::to_string(&keymap[1]).is_err());
+    }
+
+    #[test]
+    fn reject_unknown_key() {
+        #[derive(serde::Serialize)]
+        struct Structured(#[serde(with = "crate::serde_impl::structured")] Key);
+
+        let options = ParseOptions {
+            strict: false,
+            ..Default::default()
+        };
+        let key = Key::new_with_options("<c-BOO>", &options).unwrap();
+
+        assert!(serde_json::to_string(&key).is_err());
+        assert!(serde_json::to_string(&Structured(key)).is_err());
+        assert!(bincode::serialize(&key).is_err());
+
+        for json in [
+            r#""<c-BOO>""#,
+            r#"{"code":"BOO","ctrl":true}"#,
+            r#"{"code":"Unknown"}"#,
+        ] {
+            assert!(serde_json::from_str::<Key>(json).is_err(), "{json}");
+        }
+    }
+
+    #[derive(serde::Serialize, serde::Deserialize, PartialEq, Debug)]
+    struct StructuredConfig {
+        #[serde(with = "crate::serde_impl::structured")]
+        key: Key,
+    }
+
+    #[test]
+    fn structured_round_trip() {
+        let config = StructuredConfig {
+            key: Key::new("A").unwrap(),
+        };
+        let json = serde_json::to_string(&config).unwrap();
+
+        assert_eq!(
+            json,
+            r#"{"key":{"code":"a","ctrl":false,"shift":true,"alt":false}}"#
+        );
+        assert_eq!(
+            serde_json::from_str::<StructuredConfig>(&json).unwrap(),
+            config
+        );
+        assert_eq!(
+            serde_json::from_str::<StructuredConfig>(r#"{"key":"<c-CR>"}"#)
+                .unwrap()
+                .key,
+            Key::new("<c-cr>").unwrap()
+        );
+    }
+
+    #[test]
+    fn key_from_either_form() {
+        let key = Key::new("<c-a-Up>").unwrap();
+
+        assert_eq!(serde_json::to_string(&key).unwrap(), "\"<c-a-up>\"");
+        assert_eq!(serde_json::from_str::<Key>("\"<c-a-Up>\"").unwrap(), key);
+        assert_eq!(
+            serde_json::from_str::<Key>(r#"{"code":"Up","ctrl":true,"alt":true}"#).unwrap(),
+            key
+        );
+        assert!(serde_json::from_str::<Key>(r#"{"code":"Upp"}"#).is_err());
+        assert!(serde_json::from_str::<Key>("1").is_err());
+    }
+
+    #[test]
+    fn binary_round_trip() {
+        let key = Key::new("<c-s-a>").unwrap();
+        let keymap = Keymap::new("<c-b>j<BackTab><F12>").unwrap();
+        let config = Config {
+            key,
+            keymap: keymap.clone(),
+        };
+
+        let key_bytes = bincode::serialize(&key).unwrap();
+        let keymap_bytes = bincode::serialize(&keymap).unwrap();
+
+        assert_eq!(key_bytes, [b'A', 0b011]);
+        assert_eq!(keymap_bytes.len(), 8 + keymap.len() * 2);
+        assert_eq!(bincode::deserialize::<Key>(&key_bytes).unwrap(), key);
+        assert_eq!(
+            bincode::deserialize::<Keymap>(&keymap_bytes).unwrap(),
+            keymap
+        );
+        assert_eq!(
+            bincode::deserialize::<Config>(&bincode::serialize(&config).unwrap()).unwrap(),
+            config
+        );
+        assert_eq!(
+            serde_json::to_string(&config).unwrap(),
+            r#"{"key":"<c-s-a>","keymap":"<c-b>j<backtab><f12>"}"#
+        );
+    }
+
+    #[test]
+    fn invalid_binary() {
+        let options = ParseOptions {
+            unicode: true,
+            ..Default::default()
+        };
+        let unicode = Keymap::new_with_options("aé", &options).unwrap();
+
+        assert!(bincode::serialize(&unicode).is_err());
+        assert!(bincode::serialize(&unicode[1]).is_err());
+        assert!(bincode::deserialize::<Key>(&[0, 0]).is_err());
+        assert!(bincode::deserialize::<Key>(&[b'a', 0]).is_err());
+        assert!(bincode::deserialize::<Key>(&[b'A', 0b1000]).is_err());
+        assert!(bincode::deserialize::<Keymap>(&[1, 0, 0, 0, 0, 0, 0, 0, b'A']).is_err());
+    }
+
+    #[test]
+    fn keymap_alternate_spelling() {
+        let keymap = Keymap::new("<Enter><ESC><c-Space>A<F1>").unwrap();
+        let json = serde_json::to_string(&keymap).unwrap();
+
+        assert_eq!(json, "\"<cr><esc><c-space>A<f1>\"");
+        assert_eq!(serde_json::from_str::<Keymap>(&json).unwrap(), keymap);
+        assert_eq!(
+            serde_json::from_str::<Keymap>("\"<CR><ESC><c-SPACE>A<F1>\"").unwrap(),
+            keymap
+        );
+    }
+
+    #[test]
+    fn keymap_from_either_form() {
+        let keymap = Keymap::new("<c-b>jj").unwrap();
+
+        assert_eq!(
+            serde_json::from_str::<Keymap>("\"<c-b>jj\"").unwrap(),
+            keymap
+        );
+        assert_eq!(
+            serde_json::from_str::<Keymap>(r#"["<c-b>", "j", "j"]"#).unwrap(),
+            keymap
+        );
+        assert_eq!(
+            serde_json::from_str::<Keymap>("[]").unwrap(),
+            Keymap::new("").unwrap()
+        );
+        assert!(serde_json::from_str::<Keymap>(r#"["<c-b>", "jj"]"#).is_err());
+        assert!(serde_json::from_str::<Keymap>("1").is_err());
     }
 }
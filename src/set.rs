@@ -0,0 +1,138 @@
+use alloc::collections::BTreeMap;
+use core::ops::Bound;
+
+use crate::Keymap;
+
+/// Sorted set of the [Keymap] bindings, for the stateless lookups.
+///
+/// Unlike [KeymapTrie](crate::KeymapTrie), it doesn't hold the keys fed so far,
+/// the caller keeps the typed [Keymap] and queries it.
+///
+/// # Example
+///
+/// ```
+/// use viks::{Keymap, KeymapSet};
+///
+/// # fn main() {
+/// let mut set = KeymapSet::new();
+///
+/// set.insert(Keymap::new("gg").unwrap(), "top");
+/// set.insert(Keymap::new("gd").unwrap(), "definition");
+///
+/// let typed = Keymap::new("g").unwrap();
+///
+/// assert_eq!(set.get(&typed), None);
+/// assert!(set.is_prefix(&typed));
+/// assert_eq!(set.get(&Keymap::new("gd").unwrap()), Some(&"definition"));
+/// # }
+/// ```
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub struct KeymapSet<T> {
+    bindings: BTreeMap<Keymap, T>,
+}
+
+impl<T> KeymapSet<T> {
+    /// Create new empty set.
+    pub fn new() -> Self {
+        KeymapSet {
+            bindings: BTreeMap::new(),
+        }
+    }
+
+    /// Register the keymap, returns the old value if the keymap is already registered.
+    pub fn insert(&mut self, keymap: Keymap, value: T) -> Option<T> {
+        self.bindings.insert(keymap, value)
+    }
+
+    /// Unregister the keymap, returns the value if the keymap was registered.
+    pub fn remove(&mut self, keymap: &Keymap) -> Option<T> {
+        self.bindings.remove(keymap)
+    }
+
+    /// Get the value of the exactly matched keymap.
+    pub fn get(&self, keymap: &Keymap) -> Option<&T> {
+        self.bindings.get(keymap)
+    }
+
+    /// Returns `true` if the `keymap` is the proper prefix of any registered keymap.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use viks::{Keymap, KeymapSet};
+    ///
+    /// # fn main() {
+    /// let mut set = KeymapSet::new();
+    ///
+    /// set.insert(Keymap::new("<c-w>j").unwrap(), ());
+    ///
+    /// assert!(set.is_prefix(&Keymap::new("<c-w>").unwrap()));
+    /// assert!(!set.is_prefix(&Keymap::new("<c-w>j").unwrap()));
+    /// assert!(!set.is_prefix(&Keymap::new("j").unwrap()));
+    /// # }
+    /// ```
+    pub fn is_prefix(&self, keymap: &Keymap) -> bool {
+        // the keymaps starting with `keymap` are sorted right after it
+        self.bindings
+            .range((Bound::Excluded(keymap), Bound::Unbounded))
+            .next()
+            .is_some_and(|(registered, _)| registered.starts_with(keymap))
+    }
+
+    /// Get the number of the registered keymaps.
+    pub fn len(&self) -> usize {
+        self.bindings.len()
+    }
+
+    /// Returns `true` if no keymap is registered.
+    pub fn is_empty(&self) -> bool {
+        self.bindings.is_empty()
+    }
+}
+
+impl<T> Default for KeymapSet<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    fn keymap(s: &str) -> Keymap {
+        Keymap::new(s).unwrap()
+    }
+
+    #[test]
+    fn overlapping_bindings() {
+        let mut set = KeymapSet::new();
+
+        assert_eq!(set.insert(keymap("g"), 1), None);
+        assert_eq!(set.insert(keymap("gg"), 2), None);
+        assert_eq!(set.insert(keymap("gd"), 3), None);
+        assert_eq!(set.insert(keymap("<c-w>j"), 4), None);
+        assert_eq!(set.insert(keymap("<s-z>Z"), 5), None);
+        assert_eq!(set.insert(keymap("ZZ"), 6), Some(5));
+
+        assert_eq!(set.len(), 5);
+        assert_eq!(set.get(&keymap("g")), Some(&1));
+        assert_eq!(set.get(&keymap("gg")), Some(&2));
+        assert_eq!(set.get(&keymap("<c-w>")), None);
+        assert_eq!(set.get(&keymap("ZZ")), Some(&6));
+
+        assert!(set.is_prefix(&keymap("")));
+        assert!(set.is_prefix(&keymap("g")));
+        assert!(set.is_prefix(&keymap("<c-w>")));
+        assert!(set.is_prefix(&keymap("Z")));
+        assert!(!set.is_prefix(&keymap("gg")));
+        assert!(!set.is_prefix(&keymap("<c-w>k")));
+        assert!(!set.is_prefix(&keymap("z")));
+
+        assert_eq!(set.remove(&keymap("gg")), Some(2));
+        assert!(set.is_prefix(&keymap("g")));
+        assert_eq!(set.remove(&keymap("gd")), Some(3));
+        assert!(!set.is_prefix(&keymap("g")));
+        assert!(!KeymapSet::<()>::new().is_prefix(&keymap("")));
+    }
+}
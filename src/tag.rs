@@ -0,0 +1,84 @@
+use alloc::boxed::Box;
+use core::ptr;
+use core::sync::atomic::{AtomicPtr, Ordering};
+
+/// Interned text of the unknown tag, see [ParseOptions::strict](crate::ParseOptions::strict).
+///
+/// The same text is the same tag, so the tag is compared by the id without the text.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub(crate) struct Tag(pub(crate) u32);
+
+struct Node {
+    id: u32,
+    text: Box<str>,
+    next: Option<&'static Node>,
+}
+
+// the interned texts are never freed, they are as many as the distinct unknown tags
+static TAGS: AtomicPtr<Node> = AtomicPtr::new(ptr::null_mut());
+
+fn head() -> Option<&'static Node> {
+    // SAFETY: the pointer is null or leaked by `Tag::intern`, and the node is never freed
+    unsafe { TAGS.load(Ordering::Acquire).as_ref() }
+}
+
+fn nodes(head: Option<&'static Node>) -> impl Iterator<Item = &'static Node> {
+    core::iter::successors(head, |node| node.next)
+}
+
+impl Tag {
+    /// Get the tag of the text, the new text is kept for the rest of the program.
+    pub(crate) fn intern(text: &str) -> Tag {
+        loop {
+            let head = head();
+
+            if let Some(node) = nodes(head).find(|node| &*node.text == text) {
+                return Tag(node.id);
+            }
+
+            let node = Box::into_raw(Box::new(Node {
+                id: head.map_or(0, |node| node.id + 1),
+                text: text.into(),
+                next: head,
+            }));
+            let current = head.map_or(ptr::null_mut(), |node| ptr::from_ref(node).cast_mut());
+
+            match TAGS.compare_exchange(current, node, Ordering::AcqRel, Ordering::Acquire) {
+                // SAFETY: the node is just published and never freed
+                Ok(_) => return Tag(unsafe { (*node).id }),
+                // SAFETY: the node is not published, another text was interned first
+                Err(_) => drop(unsafe { Box::from_raw(node) }),
+            }
+        }
+    }
+
+    /// Get the interned text.
+    pub(crate) fn as_str(self) -> &'static str {
+        nodes(head())
+            .find(|node| node.id == self.0)
+            .map(|node| &*node.text)
+            .expect("tag is interned")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tag::*;
+
+    #[test]
+    fn intern_tags() {
+        let tag = Tag::intern("Interned");
+
+        assert_eq!(Tag::intern("Interned"), tag);
+        assert_ne!(Tag::intern("interned"), tag);
+        assert_eq!(tag.as_str(), "Interned");
+
+        let threads = (0..8)
+            .map(|_| std::thread::spawn(|| Tag::intern("Threaded")))
+            .collect::<Vec<_>>();
+
+        for thread in threads {
+            assert_eq!(thread.join().unwrap(), Tag::intern("Threaded"));
+        }
+    }
+}
@@ -0,0 +1,239 @@
+//! Termion implemetations.
+//!
+//! This implements are enable with `termion` feature.  
+//! ```sh
+//! viks = { version = "*", features = ["termion"] }
+//! ```
+//!
+//! Termion has the single modifier of the char, e.g. `Ctrl('a')` or `Alt('a')`,
+//! and Shift, Alt or Ctrl only for the arrows.
+//! The key with other combinations, e.g. `<c-a-x>` or `<c-s-a>`, is not available as the termion key.
+//!
+//! The Enter is `Char('\n')`, the Tab is `Char('\t')`,
+//! and the non-ascii char is the unicode key, see [ParseOptions::unicode](crate::ParseOptions::unicode).
+//!
+//! ```
+//! use termion::event::Key as TermKey;
+//! use viks::Key;
+//!
+//! # fn main() {
+//! assert_eq!(Key::try_from(TermKey::Ctrl('w')).unwrap(), Key::new("<c-w>").unwrap());
+//! assert_eq!(TermKey::try_from(Key::new("<a-A>").unwrap()).unwrap(), TermKey::Alt('A'));
+//! # }
+//! ```
+
+use termion::event::Key as TermKey;
+
+use crate::{Code, Error, ErrorKind, Key, KeyCode, KeyModifiers, Leader, Payload};
+
+impl TryFrom<TermKey> for Key {
+    type Error = Error;
+
+    fn try_from(value: TermKey) -> Result<Self, Self::Error> {
+        let unsupported = || Error::new(ErrorKind::UnknownKey, &format!("{value:?}"));
+
+        let char_key = |c: char| match c {
+            '\n' => Ok(Key::ENTER),
+            c if !c.is_ascii() => Ok(Key::from_unicode(c)),
+            c => Key::try_from(c).map_err(|_| unsupported()),
+        };
+
+        let named = |code: KeyCode, modifiers: KeyModifiers| {
+            Key {
+                code,
+                modifiers: KeyModifiers::NONE,
+                payload: Payload::NONE,
+                leader: Leader::No,
+            }
+            .with_modifiers(modifiers)
+        };
+
+        let key = match value {
+            TermKey::Char(c) => char_key(c)?,
+            TermKey::Alt(c) => char_key(c)?.with_alt(),
+            TermKey::Ctrl(c) => char_key(c)?.with_ctrl(),
+            TermKey::Null => Key::from_byte(0)?,
+            TermKey::Backspace => Key::BACKSPACE,
+            TermKey::Left => named(KeyCode::Left, KeyModifiers::NONE),
+            TermKey::ShiftLeft => named(KeyCode::Left, KeyModifiers::SHIFT),
+            TermKey::AltLeft => named(KeyCode::Left, KeyModifiers::ALT),
+            TermKey::CtrlLeft => named(KeyCode::Left, KeyModifiers::CONTROL),
+            TermKey::Right => named(KeyCode::Right, KeyModifiers::NONE),
+            TermKey::ShiftRight => named(KeyCode::Right, KeyModifiers::SHIFT),
+            TermKey::AltRight => named(KeyCode::Right, KeyModifiers::ALT),
+            TermKey::CtrlRight => named(KeyCode::Right, KeyModifiers::CONTROL),
+            TermKey::Up => named(KeyCode::Up, KeyModifiers::NONE),
+            TermKey::ShiftUp => named(KeyCode::Up, KeyModifiers::SHIFT),
+            TermKey::AltUp => named(KeyCode::Up, KeyModifiers::ALT),
+            TermKey::CtrlUp => named(KeyCode::Up, KeyModifiers::CONTROL),
+            TermKey::Down => named(KeyCode::Down, KeyModifiers::NONE),
+            TermKey::ShiftDown => named(KeyCode::Down, KeyModifiers::SHIFT),
+            TermKey::AltDown => named(KeyCode::Down, KeyModifiers::ALT),
+            TermKey::CtrlDown => named(KeyCode::Down, KeyModifiers::CONTROL),
+            TermKey::Home => named(KeyCode::Home, KeyModifiers::NONE),
+            TermKey::CtrlHome => named(KeyCode::Home, KeyModifiers::CONTROL),
+            TermKey::End => named(KeyCode::End, KeyModifiers::NONE),
+            TermKey::CtrlEnd => named(KeyCode::End, KeyModifiers::CONTROL),
+            TermKey::PageUp => named(KeyCode::PageUp, KeyModifiers::NONE),
+            TermKey::PageDown => named(KeyCode::PageDown, KeyModifiers::NONE),
+            TermKey::BackTab => named(KeyCode::BackTab, KeyModifiers::NONE),
+            TermKey::Delete => Key::DELETE,
+            TermKey::Insert => named(KeyCode::Insert, KeyModifiers::NONE),
+            TermKey::F(n) => named(
+                KeyCode::from_function(n).ok_or_else(unsupported)?,
+                KeyModifiers::NONE,
+            ),
+            TermKey::Esc => Key::ESC,
+            _ => return Err(unsupported()),
+        };
+
+        Ok(key)
+    }
+}
+
+impl TryFrom<Key> for TermKey {
+    type Error = Error;
+
+    fn try_from(value: Key) -> Result<Self, Self::Error> {
+        let unsupported = || Error::new(ErrorKind::UnknownKey, &value.to_string());
+
+        if value.to_byte() == Some(0) {
+            return Ok(TermKey::Null);
+        }
+
+        let ctrl = value.modifiers.is_ctrl();
+        let alt = value.modifiers.is_alt();
+        // the shift of the alphabet is the uppercase char
+        let shift = value.modifiers.is_shift() && !value.is_alpha();
+
+        let key = match (value.code(), ctrl, alt, shift) {
+            (Code::Char(c), false, false, false)
+                if value.is_alpha() && value.modifiers.is_shift() =>
+            {
+                TermKey::Char(c.to_ascii_uppercase())
+            }
+            (Code::Char(c), false, true, false)
+                if value.is_alpha() && value.modifiers.is_shift() =>
+            {
+                TermKey::Alt(c.to_ascii_uppercase())
+            }
+            (Code::Char(_), true, false, false)
+                if value.is_alpha() && value.modifiers.is_shift() =>
+            {
+                return Err(unsupported());
+            }
+            (Code::Char(c), false, false, false) => TermKey::Char(c),
+            (Code::Char(c), false, true, false) => TermKey::Alt(c),
+            (Code::Char(c), true, false, false) => TermKey::Ctrl(c),
+            (Code::Enter, false, false, false) => TermKey::Char('\n'),
+            (Code::Enter, false, true, false) => TermKey::Alt('\n'),
+            (Code::Tab, false, false, false) => TermKey::Char('\t'),
+            (Code::Tab, false, true, false) => TermKey::Alt('\t'),
+            (Code::Backspace, false, false, false) => TermKey::Backspace,
+            (Code::Delete, false, false, false) => TermKey::Delete,
+            (Code::Esc, false, false, false) => TermKey::Esc,
+            (Code::Left, false, false, false) => TermKey::Left,
+            (Code::Left, false, false, true) => TermKey::ShiftLeft,
+            (Code::Left, false, true, false) => TermKey::AltLeft,
+            (Code::Left, true, false, false) => TermKey::CtrlLeft,
+            (Code::Right, false, false, false) => TermKey::Right,
+            (Code::Right, false, false, true) => TermKey::ShiftRight,
+            (Code::Right, false, true, false) => TermKey::AltRight,
+            (Code::Right, true, false, false) => TermKey::CtrlRight,
+            (Code::Up, false, false, false) => TermKey::Up,
+            (Code::Up, false, false, true) => TermKey::ShiftUp,
+            (Code::Up, false, true, false) => TermKey::AltUp,
+            (Code::Up, true, false, false) => TermKey::CtrlUp,
+            (Code::Down, false, false, false) => TermKey::Down,
+            (Code::Down, false, false, true) => TermKey::ShiftDown,
+            (Code::Down, false, true, false) => TermKey::AltDown,
+            (Code::Down, true, false, false) => TermKey::CtrlDown,
+            (Code::Home, false, false, false) => TermKey::Home,
+            (Code::Home, true, false, false) => TermKey::CtrlHome,
+            (Code::End, false, false, false) => TermKey::End,
+            (Code::End, true, false, false) => TermKey::CtrlEnd,
+            (Code::PageUp, false, false, false) => TermKey::PageUp,
+            (Code::PageDown, false, false, false) => TermKey::PageDown,
+            (Code::BackTab, false, false, false) => TermKey::BackTab,
+            (Code::Insert, false, false, false) => TermKey::Insert,
+            (Code::F(n), false, false, false) => TermKey::F(n),
+            _ => return Err(unsupported()),
+        };
+
+        Ok(key)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use termion::event::Key as TermKey;
+
+    use crate::*;
+
+    #[test]
+    fn from_termion() {
+        let cases = [
+            (TermKey::Char('a'), "a"),
+            (TermKey::Char('A'), "A"),
+            (TermKey::Char('!'), "!"),
+            (TermKey::Char(' '), "<space>"),
+            (TermKey::Char('<'), "<lt>"),
+            (TermKey::Char('\n'), "<cr>"),
+            (TermKey::Char('\t'), "<tab>"),
+            (TermKey::Ctrl('w'), "<c-w>"),
+            (TermKey::Alt('x'), "<a-x>"),
+            (TermKey::Alt('X'), "<a-s-x>"),
+            (TermKey::CtrlUp, "<c-up>"),
+            (TermKey::ShiftLeft, "<s-left>"),
+            (TermKey::AltDown, "<a-down>"),
+            (TermKey::CtrlEnd, "<c-end>"),
+            (TermKey::BackTab, "<s-tab>"),
+            (TermKey::Backspace, "<bs>"),
+            (TermKey::F(5), "<F5>"),
+            (TermKey::Null, "<c-@>"),
+        ];
+
+        for (termion_key, expected) in cases {
+            assert_eq!(
+                Key::try_from(termion_key).unwrap(),
+                Key::new(expected).unwrap(),
+                "{termion_key:?}"
+            );
+        }
+
+        assert_eq!(
+            Key::try_from(TermKey::Char('é')).unwrap().code(),
+            Code::Char('é')
+        );
+        assert!(Key::try_from(TermKey::F(30)).is_err());
+    }
+
+    #[test]
+    fn round_trip() {
+        let keymap = Keymap::new(
+            "aA;<c-w><a-x><a-X><cr><tab><a-cr><space><lt><c-@><BS><Del><Esc>\
+             <Up><s-Down><a-Left><c-Right><Home><c-End><PageUp><BackTab><Insert><F12>",
+        )
+        .unwrap();
+
+        for key in &keymap {
+            let termion_key = TermKey::try_from(*key).unwrap();
+
+            assert_eq!(Key::try_from(termion_key).unwrap(), *key, "{key}");
+        }
+    }
+
+    #[test]
+    fn unsupported_combinations() {
+        for tag in [
+            "<c-s-a>",
+            "<c-a-x>",
+            "<s-home>",
+            "<c-cr>",
+            "<c-F1>",
+            "<LeftMouse>",
+        ] {
+            assert!(TermKey::try_from(Key::new(tag).unwrap()).is_err(), "{tag}");
+        }
+    }
+}
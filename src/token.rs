@@ -0,0 +1,71 @@
+use crate::{Error, ErrorKind, Key};
+use alloc::string::{String, ToString};
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// Flat representation of [Key] for the data interchange.
+///
+/// `name` is the char for printable keys (lowercase for alphabetic) or
+/// the key name for special keys, e.g. `CR`.
+/// The unknown key, see [Code::Unknown](crate::Code::Unknown), is named by its tag, e.g. `BOO`,
+/// and the name is rejected when converted back as the strict parse.
+///
+/// # Example
+///
+/// ```
+/// use viks::{Key, KeyToken};
+///
+/// # fn main() {
+/// let token = KeyToken::from(Key::new("<c-cr>").unwrap());
+///
+/// assert_eq!(token.name, "CR");
+/// assert!(token.ctrl);
+/// assert_eq!(Key::try_from(&token).unwrap(), Key::new("<c-cr>").unwrap());
+/// # }
+/// ```
+pub struct KeyToken {
+    /// Key name or char.
+    pub name: String,
+    /// Control modifier.
+    pub ctrl: bool,
+    /// Shift modifier.
+    pub shift: bool,
+    /// Alt modifier.
+    pub alt: bool,
+    /// Super modifier, this is not supported by [Key] yet.
+    #[cfg_attr(feature = "serde", serde(rename = "super"))]
+    pub super_: bool,
+}
+
+impl From<Key> for KeyToken {
+    fn from(value: Key) -> Self {
+        let name = match (value.payload.char(), value.payload.tag(), value.code.name()) {
+            (Some(c), _, _) => c.to_string(),
+            (_, Some(tag), _) => tag.as_str().to_string(),
+            (_, _, Some(name)) => name.to_string(),
+            _ => value.code.as_ascii().to_ascii_lowercase().to_string(),
+        };
+
+        KeyToken {
+            name,
+            ctrl: value.modifiers.is_ctrl(),
+            shift: value.modifiers.is_shift(),
+            alt: value.modifiers.is_alt(),
+            super_: false,
+        }
+    }
+}
+
+impl TryFrom<&KeyToken> for Key {
+    type Error = Error;
+
+    fn try_from(value: &KeyToken) -> Result<Self, Self::Error> {
+        let key = Key::from_parts(&value.name, value.shift, value.ctrl, value.alt)?;
+
+        if value.super_ {
+            return Err(Error::new(ErrorKind::InvalidModifier, &value.name));
+        }
+
+        Ok(key)
+    }
+}
@@ -0,0 +1,216 @@
+use alloc::collections::BTreeMap;
+use alloc::vec;
+use alloc::vec::Vec;
+
+use crate::{Key, Keymap};
+
+struct Node<T> {
+    children: BTreeMap<Key, usize>,
+    value: Option<T>,
+}
+
+impl<T> Node<T> {
+    fn new() -> Self {
+        Node {
+            children: BTreeMap::new(),
+            value: None,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+/// Result of [KeymapTrie::step].
+pub enum Step<'a, T> {
+    /// The input keys don't match any keymap, the trie is reset.
+    NoMatch,
+    /// The input keys are the prefix of some keymaps, waiting for the next key.
+    Pending,
+    /// The input keys matched the keymap, the trie is reset.
+    Matched(&'a T),
+}
+
+/// Prefix-matching dispatcher for the registered [Keymap]s.
+///
+/// When a keymap is also the prefix of other keymaps (e.g. `g` and `gg`),
+/// [step](KeymapTrie::step) waits with [Step::Pending],
+/// use [pending](KeymapTrie::pending) to take the shorter one, e.g. on timeout.
+///
+/// # Example
+///
+/// ```
+/// use viks::{Key, Keymap, KeymapTrie, Step};
+///
+/// # fn main() {
+/// let mut trie = KeymapTrie::new();
+///
+/// trie.insert(Keymap::new("gg").unwrap(), "top");
+/// trie.insert(Keymap::new("gd").unwrap(), "definition");
+///
+/// let g = Key::new("g").unwrap();
+/// let d = Key::new("d").unwrap();
+///
+/// assert_eq!(trie.step(&g), Step::Pending);
+/// assert_eq!(trie.step(&d), Step::Matched(&"definition"));
+/// # }
+/// ```
+pub struct KeymapTrie<T> {
+    nodes: Vec<Node<T>>,
+    cursor: usize,
+}
+
+impl<T> KeymapTrie<T> {
+    /// Create new empty trie.
+    pub fn new() -> Self {
+        KeymapTrie {
+            nodes: vec![Node::new()],
+            cursor: 0,
+        }
+    }
+
+    /// Register the keymap, returns the old value if the keymap is already registered.
+    pub fn insert(&mut self, keymap: Keymap, value: T) -> Option<T> {
+        let mut node = 0;
+
+        for key in keymap {
+            node = match self.nodes[node].children.get(&key) {
+                Some(child) => *child,
+                None => {
+                    let child = self.nodes.len();
+
+                    self.nodes.push(Node::new());
+                    self.nodes[node].children.insert(key, child);
+
+                    child
+                }
+            };
+        }
+
+        self.nodes[node].value.replace(value)
+    }
+
+    /// Get the value of the keymap.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use viks::{Keymap, KeymapTrie};
+    ///
+    /// # fn main() {
+    /// let mut trie = KeymapTrie::new();
+    ///
+    /// trie.insert(Keymap::new("<leader>w").unwrap(), "write");
+    ///
+    /// assert_eq!(trie.get(&Keymap::new("<space>w").unwrap()), Some(&"write"));
+    /// assert_eq!(trie.get(&Keymap::new("<space>").unwrap()), None);
+    /// # }
+    /// ```
+    pub fn get(&self, keymap: &Keymap) -> Option<&T> {
+        let mut node = 0;
+
+        for key in keymap {
+            node = *self.nodes[node].children.get(key)?;
+        }
+
+        self.nodes[node].value.as_ref()
+    }
+
+    /// Feed the key and advance the matching state.
+    pub fn step(&mut self, key: &Key) -> Step<'_, T> {
+        let Some(&child) = self.nodes[self.cursor].children.get(key) else {
+            self.cursor = 0;
+
+            return Step::NoMatch;
+        };
+
+        if !self.nodes[child].children.is_empty() {
+            self.cursor = child;
+
+            return Step::Pending;
+        }
+
+        self.cursor = 0;
+
+        match &self.nodes[child].value {
+            Some(value) => Step::Matched(value),
+            None => Step::NoMatch,
+        }
+    }
+
+    /// Get the value of the keys fed so far, if it is registered.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use viks::{Key, Keymap, KeymapTrie, Step};
+    ///
+    /// # fn main() {
+    /// let mut trie = KeymapTrie::new();
+    ///
+    /// trie.insert(Keymap::new("g").unwrap(), "go");
+    /// trie.insert(Keymap::new("gg").unwrap(), "top");
+    ///
+    /// assert_eq!(trie.step(&Key::new("g").unwrap()), Step::Pending);
+    /// assert_eq!(trie.pending(), Some(&"go"));
+    /// # }
+    /// ```
+    pub fn pending(&self) -> Option<&T> {
+        self.nodes[self.cursor].value.as_ref()
+    }
+
+    /// Discard the keys fed so far.
+    pub fn reset(&mut self) {
+        self.cursor = 0;
+    }
+}
+
+impl<T> Default for KeymapTrie<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    fn key(s: &str) -> Key {
+        Key::new(s).unwrap()
+    }
+
+    #[test]
+    fn overlapping_prefixes() {
+        let mut trie = KeymapTrie::new();
+
+        trie.insert(Keymap::new("gg").unwrap(), 1);
+        trie.insert(Keymap::new("gd").unwrap(), 2);
+        trie.insert(Keymap::new("<c-w>j").unwrap(), 3);
+
+        assert_eq!(trie.step(&key("g")), Step::Pending);
+        assert_eq!(trie.step(&key("g")), Step::Matched(&1));
+        assert_eq!(trie.step(&key("g")), Step::Pending);
+        assert_eq!(trie.step(&key("d")), Step::Matched(&2));
+        assert_eq!(trie.step(&key("g")), Step::Pending);
+        assert_eq!(trie.step(&key("x")), Step::NoMatch);
+        assert_eq!(trie.step(&key("<c-w>")), Step::Pending);
+        assert_eq!(trie.step(&key("j")), Step::Matched(&3));
+        assert_eq!(trie.step(&key("j")), Step::NoMatch);
+    }
+
+    #[test]
+    fn shorter_keymap() {
+        let mut trie = KeymapTrie::new();
+
+        assert_eq!(trie.insert(Keymap::new("g").unwrap(), 1), None);
+        assert_eq!(trie.insert(Keymap::new("gg").unwrap(), 2), None);
+        assert_eq!(trie.insert(Keymap::new("gg").unwrap(), 3), Some(2));
+
+        assert_eq!(trie.step(&key("g")), Step::Pending);
+        assert_eq!(trie.pending(), Some(&1));
+
+        trie.reset();
+
+        assert_eq!(trie.pending(), None);
+        assert_eq!(trie.get(&Keymap::new("gg").unwrap()), Some(&3));
+        assert_eq!(trie.get(&Keymap::new("gd").unwrap()), None);
+    }
+}
@@ -0,0 +1,38 @@
+#![no_std]
+
+extern crate alloc;
+extern crate std;
+
+use alloc::string::ToString;
+use std::process::Command;
+use viks::{Key, Keymap};
+
+#[test]
+fn parse_without_std() {
+    let key = Key::new("<c-a>").unwrap();
+    let keymap = Keymap::new("<leader>gg").unwrap();
+
+    assert_eq!(key.to_string(), "<c-a>");
+    assert_eq!(keymap.len(), 3);
+    assert!(Key::new("<unknown>").is_err());
+}
+
+// the tests always link `std`, so build the crate itself without the `std` feature,
+// same as the `no_std` job of CI without the target of no `std`
+#[test]
+fn build_without_std() {
+    for features in ["", "serde"] {
+        let status = Command::new(env!("CARGO"))
+            .args(["build", "--quiet", "--lib", "--no-default-features"])
+            .args(["--features", features])
+            .env(
+                "CARGO_TARGET_DIR",
+                concat!(env!("CARGO_TARGET_TMPDIR"), "/no_std"),
+            )
+            .current_dir(env!("CARGO_MANIFEST_DIR"))
+            .status()
+            .unwrap();
+
+        assert!(status.success(), "--features '{features}'");
+    }
+}